@@ -27,6 +27,87 @@ pub const GRAY_CHARS: &str = "▁▂▃█";
 /// Triangle decoration characters
 pub const TRI_CHARS: &str = "◢◣◤◥";
 
+/// Filled diamond line-end characters
+pub const DIAMOND_END_FILLED_CHARS: &str = "◆";
+/// Open (unfilled) diamond line-end characters
+pub const DIAMOND_END_OPEN_CHARS: &str = "◇";
+/// Filled circle line-end characters (distinct from the plain [`POINT_CHARS`] dots: these sit at
+/// the end of a line and take on its direction, rather than standing alone)
+pub const CIRCLE_END_FILLED_CHARS: &str = "◉";
+/// Open (unfilled) circle line-end characters
+pub const CIRCLE_END_OPEN_CHARS: &str = "◯";
+/// Cross/plus line-end characters
+pub const CROSS_END_CHARS: &str = "#";
+
+/// Box-drawing corners, tees and the cross junction (U+2500 block), excluding the plain straight
+/// single/double lines (`─│═║`) which are vertex-adjacent but not junctions themselves. Covers
+/// single-weight, double-weight, rounded, and mixed single/double corners.
+pub const BOX_JUNCTION_CHARS: &str =
+    "┌┐└┘├┤┬┴┼╭╮╰╯╔╗╚╝╠╣╦╩╬╒╕╘╛╓╖╙╜╞╡╟╢╤╧╥╨╪╫";
+
+/// Box-drawing chars whose horizontal arm (if any) is a single-weight line
+const BOX_H_SINGLE_CHARS: &str = "┌┐└┘├┤┬┴┼╭╮╰╯╓╖╙╜╟╢╥╨╫";
+/// Box-drawing chars whose horizontal arm (if any) is a double-weight line
+const BOX_H_DOUBLE_CHARS: &str = "╔╗╚╝╠╣╦╩╬╒╕╘╛╞╡╤╧╪";
+/// Box-drawing chars whose vertical arm (if any) is a single-weight line
+const BOX_V_SINGLE_CHARS: &str = "┌┐└┘├┤┬┴┼╭╮╰╯╒╕╘╛╞╡╤╧╪";
+/// Box-drawing chars whose vertical arm (if any) is a double-weight line
+const BOX_V_DOUBLE_CHARS: &str = "╔╗╚╝╠╣╦╩╬╓╖╙╜╟╢╥╨╫";
+
+// ============================================================================
+// Direction bitmask
+// ============================================================================
+
+/// Bitmask of the four grid directions a character's line segments connect to, as reported by
+/// [`connections`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dirs(u8);
+
+impl Dirs {
+    pub const NONE: Dirs = Dirs(0);
+    pub const UP: Dirs = Dirs(0b0001);
+    pub const DOWN: Dirs = Dirs(0b0010);
+    pub const LEFT: Dirs = Dirs(0b0100);
+    pub const RIGHT: Dirs = Dirs(0b1000);
+
+    /// Whether this mask includes every direction set in `other`
+    #[inline]
+    pub fn contains(self, other: Dirs) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for Dirs {
+    type Output = Dirs;
+    fn bitor(self, rhs: Dirs) -> Dirs {
+        Dirs(self.0 | rhs.0)
+    }
+}
+
+/// Which of the four grid directions (up/down/left/right) a character's line segments connect
+/// to, e.g. `┬` connects left/right/down and `└` connects up/right. Covers ASCII vertex chars
+/// (`+ . , ' \``), the plain single/double straight lines, and the full box-drawing corner/tee/
+/// cross set (single, double, rounded, and mixed-weight). Unrecognized characters (including
+/// diagonals, which don't fall on the orthogonal grid `Dirs` models) report [`Dirs::NONE`].
+pub fn connections(c: char) -> Dirs {
+    match c {
+        '+' | '┼' | '╬' | '╪' | '╫' => Dirs::UP | Dirs::DOWN | Dirs::LEFT | Dirs::RIGHT,
+        '.' | ',' => Dirs::DOWN,
+        '\'' | '`' => Dirs::UP,
+        '┬' | '╦' | '╤' | '╥' => Dirs::DOWN | Dirs::LEFT | Dirs::RIGHT,
+        '┴' | '╩' | '╧' | '╨' => Dirs::UP | Dirs::LEFT | Dirs::RIGHT,
+        '├' | '╠' | '╞' | '╟' => Dirs::UP | Dirs::DOWN | Dirs::RIGHT,
+        '┤' | '╣' | '╡' | '╢' => Dirs::UP | Dirs::DOWN | Dirs::LEFT,
+        '┌' | '╭' | '╔' | '╒' | '╓' => Dirs::DOWN | Dirs::RIGHT,
+        '┐' | '╮' | '╗' | '╕' | '╖' => Dirs::DOWN | Dirs::LEFT,
+        '└' | '╰' | '╚' | '╘' | '╙' => Dirs::UP | Dirs::RIGHT,
+        '┘' | '╯' | '╝' | '╛' | '╜' => Dirs::UP | Dirs::LEFT,
+        '-' | '─' | '=' | '═' | '~' | '(' | ')' => Dirs::LEFT | Dirs::RIGHT,
+        '|' | '│' | '║' => Dirs::UP | Dirs::DOWN,
+        _ => Dirs::NONE,
+    }
+}
+
 // ============================================================================
 // Vertex classification
 // ============================================================================
@@ -34,7 +115,7 @@ pub const TRI_CHARS: &str = "◢◣◤◥";
 /// Returns true if the character is part of the line network (a vertex/junction)
 #[inline]
 pub fn is_vertex(c: char) -> bool {
-    VERTEX_CHARS.contains(c)
+    VERTEX_CHARS.contains(c) || BOX_JUNCTION_CHARS.contains(c)
 }
 
 /// Returns true if the character is an undirected vertex (+)
@@ -44,18 +125,32 @@ pub fn is_undirected_vertex(c: char) -> bool {
     c == '+'
 }
 
-/// Returns true if the character can serve as a top vertex (., , or +)
-/// These connect to lines going down
+/// Returns true if the character can serve as a top vertex (a vertical line below it can hang
+/// from it): `.`, `,`, `+`, or a box-drawing junction connecting downward (e.g. `┌┬┐`)
 #[inline]
 pub fn is_top_vertex(c: char) -> bool {
-    c == '.' || c == ',' || c == '+'
+    c == '.' || c == ',' || c == '+' || (BOX_JUNCTION_CHARS.contains(c) && connections(c).contains(Dirs::DOWN))
 }
 
-/// Returns true if the character can serve as a bottom vertex (', `, or +)
-/// These connect to lines going up
+/// Returns true if the character can serve as a bottom vertex (a vertical line above it can hang
+/// from it): `'`, `` ` ``, `+`, or a box-drawing junction connecting upward (e.g. `└┴┘`)
 #[inline]
 pub fn is_bottom_vertex(c: char) -> bool {
-    c == '\'' || c == '`' || c == '+'
+    c == '\'' || c == '`' || c == '+' || (BOX_JUNCTION_CHARS.contains(c) && connections(c).contains(Dirs::UP))
+}
+
+/// Returns true if the character can serve as a left vertex (a horizontal line to its right can
+/// hang from it): `+`, or a box-drawing junction connecting rightward (e.g. `├┌└`)
+#[inline]
+pub fn is_left_vertex(c: char) -> bool {
+    c == '+' || (BOX_JUNCTION_CHARS.contains(c) && connections(c).contains(Dirs::RIGHT))
+}
+
+/// Returns true if the character can serve as a right vertex (a horizontal line to its left can
+/// hang from it): `+`, or a box-drawing junction connecting leftward (e.g. `┤┐┘`)
+#[inline]
+pub fn is_right_vertex(c: char) -> bool {
+    c == '+' || (BOX_JUNCTION_CHARS.contains(c) && connections(c).contains(Dirs::LEFT))
 }
 
 /// Returns true if the character is a top vertex or an upward arrow (^)
@@ -86,10 +181,11 @@ pub fn is_vertex_or_right_decoration(c: char) -> bool {
 // Line classification
 // ============================================================================
 
-/// Returns true if the character is a solid horizontal line segment
+/// Returns true if the character is a solid horizontal line segment, including box-drawing
+/// junctions whose horizontal arm is single-weight (e.g. `┬┼├`)
 #[inline]
 pub fn is_solid_h_line(c: char) -> bool {
-    c == '-' || c == '─' || c == '+' || c == '(' || c == ')'
+    c == '-' || c == '─' || c == '+' || c == '(' || c == ')' || BOX_H_SINGLE_CHARS.contains(c)
 }
 
 /// Returns true if the character is a squiggle/wave horizontal line segment
@@ -98,10 +194,11 @@ pub fn is_squiggle_h_line(c: char) -> bool {
     c == '~' || c == '+' || c == '(' || c == ')'
 }
 
-/// Returns true if the character is a double horizontal line segment
+/// Returns true if the character is a double horizontal line segment, including box-drawing
+/// junctions whose horizontal arm is double-weight (e.g. `╦╬╠`)
 #[inline]
 pub fn is_double_h_line(c: char) -> bool {
-    c == '=' || c == '═' || c == '+' || c == '(' || c == ')'
+    c == '=' || c == '═' || c == '+' || c == '(' || c == ')' || BOX_H_DOUBLE_CHARS.contains(c)
 }
 
 /// Returns true if the character is any horizontal line type
@@ -110,16 +207,18 @@ pub fn is_any_h_line(c: char) -> bool {
     is_solid_h_line(c) || is_squiggle_h_line(c) || is_double_h_line(c)
 }
 
-/// Returns true if the character is a solid vertical line segment
+/// Returns true if the character is a solid vertical line segment, including box-drawing
+/// junctions whose vertical arm is single-weight (e.g. `┬┼├`)
 #[inline]
 pub fn is_solid_v_line(c: char) -> bool {
-    c == '|' || c == '│' || c == '+'
+    c == '|' || c == '│' || c == '+' || BOX_V_SINGLE_CHARS.contains(c)
 }
 
-/// Returns true if the character is a double vertical line segment
+/// Returns true if the character is a double vertical line segment, including box-drawing
+/// junctions whose vertical arm is double-weight (e.g. `╦╬╠`)
 #[inline]
 pub fn is_double_v_line(c: char) -> bool {
-    c == '║' || c == '+'
+    c == '║' || c == '+' || BOX_V_DOUBLE_CHARS.contains(c)
 }
 
 /// Returns true if the character is a forward slash diagonal (/)
@@ -168,10 +267,46 @@ pub fn is_jump(c: char) -> bool {
     c == '(' || c == ')'
 }
 
+/// Returns true if the character is a diamond line-end marker (filled or open)
+#[inline]
+pub fn is_diamond_end(c: char) -> bool {
+    DIAMOND_END_FILLED_CHARS.contains(c) || DIAMOND_END_OPEN_CHARS.contains(c)
+}
+
+/// Returns true if the character is specifically a *filled* diamond line-end marker
+#[inline]
+pub fn is_diamond_end_filled(c: char) -> bool {
+    DIAMOND_END_FILLED_CHARS.contains(c)
+}
+
+/// Returns true if the character is a circle line-end marker (filled or open)
+#[inline]
+pub fn is_circle_end(c: char) -> bool {
+    CIRCLE_END_FILLED_CHARS.contains(c) || CIRCLE_END_OPEN_CHARS.contains(c)
+}
+
+/// Returns true if the character is specifically a *filled* circle line-end marker
+#[inline]
+pub fn is_circle_end_filled(c: char) -> bool {
+    CIRCLE_END_FILLED_CHARS.contains(c)
+}
+
+/// Returns true if the character is a cross/plus line-end marker
+#[inline]
+pub fn is_cross_end(c: char) -> bool {
+    CROSS_END_CHARS.contains(c)
+}
+
 /// Returns true if the character is any kind of decoration
 #[inline]
 pub fn is_decoration(c: char) -> bool {
-    is_arrow_head(c) || is_point(c) || is_gray(c) || is_tri(c)
+    is_arrow_head(c)
+        || is_point(c)
+        || is_gray(c)
+        || is_tri(c)
+        || is_diamond_end(c)
+        || is_circle_end(c)
+        || is_cross_end(c)
 }
 
 // ============================================================================
@@ -206,6 +341,184 @@ pub fn tri_angle(c: char) -> f64 {
     }
 }
 
+// ============================================================================
+// Configurable character classification
+// ============================================================================
+
+/// A configurable table of which characters play which role in a diagram, so a consumer can
+/// adapt the parser to their own conventions (extra point glyphs, custom shading levels, a
+/// narrower or wider line-character vocabulary) without forking the crate.
+///
+/// The free functions above (`is_solid_h_line`, `is_vertex`, `gray_level`, etc.) remain the
+/// defaults used internally wherever a [`CharSet`] isn't threaded through; [`CharSet::default`]
+/// reproduces exactly the same classification those functions implement, including full-weight
+/// Unicode box-drawing support, which is treated as a structural fact about the character set
+/// rather than a per-diagram convention and so is always recognized regardless of the fields
+/// below.
+///
+/// [`Grid`](crate::grid::Grid) stores one `CharSet` (see [`Grid::with_charset`](crate::grid::Grid::with_charset))
+/// and consults it from every `*_at` detection method.
+#[derive(Debug, Clone)]
+pub struct CharSet {
+    /// Characters that start/extend a solid horizontal line (e.g. `-`, `─`)
+    pub solid_h_chars: String,
+    /// Characters that start/extend a squiggle/wave horizontal line (e.g. `~`)
+    pub squiggle_h_chars: String,
+    /// Characters that start/extend a double-weight horizontal line (e.g. `=`, `═`)
+    pub double_h_chars: String,
+    /// Characters that start/extend a solid vertical line (e.g. `|`, `│`)
+    pub solid_v_chars: String,
+    /// Characters that start/extend a double-weight vertical line (e.g. `║`)
+    pub double_v_chars: String,
+    /// Forward slash diagonal characters (e.g. `/`, `╱`)
+    pub forward_diagonal_chars: String,
+    /// Backslash diagonal characters (e.g. `\`, `╲`)
+    pub back_diagonal_chars: String,
+    /// Vertex characters that connect lines (e.g. `+.',\``)
+    pub vertex_chars: String,
+    /// Arrow head characters that indicate line direction (e.g. `><^vV`)
+    pub arrow_head_chars: String,
+    /// Point/dot decoration glyphs (e.g. `o*◌○◍●⊕`)
+    pub point_chars: String,
+    /// Jump (bridge) characters for line crossings (e.g. `()`)
+    pub jump_chars: String,
+    /// Gray fill characters mapped to their shading level (0-255)
+    pub gray_levels: Vec<(char, u8)>,
+}
+
+impl Default for CharSet {
+    /// Reproduces exactly the classification the free functions in this module implement today.
+    fn default() -> Self {
+        Self {
+            solid_h_chars: "-─".to_string(),
+            squiggle_h_chars: "~".to_string(),
+            double_h_chars: "=═".to_string(),
+            solid_v_chars: "|│".to_string(),
+            double_v_chars: "║".to_string(),
+            forward_diagonal_chars: "/╱".to_string(),
+            back_diagonal_chars: "\\╲".to_string(),
+            vertex_chars: VERTEX_CHARS.to_string(),
+            arrow_head_chars: ARROW_HEAD_CHARS.to_string(),
+            point_chars: POINT_CHARS.to_string(),
+            jump_chars: JUMP_CHARS.to_string(),
+            gray_levels: vec![('▁', 64), ('▂', 128), ('▃', 191), ('█', 255)],
+        }
+    }
+}
+
+impl CharSet {
+    /// Returns true if the character is a vertex/junction, per [`Self::vertex_chars`] plus the
+    /// always-recognized box-drawing junction set
+    #[inline]
+    pub fn is_vertex(&self, c: char) -> bool {
+        self.vertex_chars.contains(c) || BOX_JUNCTION_CHARS.contains(c)
+    }
+
+    /// Returns true if the character can serve as a top vertex (see [`is_top_vertex`]), per
+    /// [`Self::vertex_chars`] plus the always-recognized box-drawing junction set. A configured
+    /// vertex char counts as a top vertex unless it's specifically bottom-only (`'`, `` ` ``), so a
+    /// custom char added to `vertex_chars` is, like `+`, usable from either end by default.
+    #[inline]
+    pub fn is_top_vertex(&self, c: char) -> bool {
+        (self.vertex_chars.contains(c) && c != '\'' && c != '`')
+            || (BOX_JUNCTION_CHARS.contains(c) && connections(c).contains(Dirs::DOWN))
+    }
+
+    /// Returns true if the character can serve as a bottom vertex (see [`is_bottom_vertex`]), per
+    /// [`Self::vertex_chars`] plus the always-recognized box-drawing junction set. A configured
+    /// vertex char counts as a bottom vertex unless it's specifically top-only (`.`, `,`), so a
+    /// custom char added to `vertex_chars` is, like `+`, usable from either end by default.
+    #[inline]
+    pub fn is_bottom_vertex(&self, c: char) -> bool {
+        (self.vertex_chars.contains(c) && c != '.' && c != ',')
+            || (BOX_JUNCTION_CHARS.contains(c) && connections(c).contains(Dirs::UP))
+    }
+
+    /// Returns true if the character is a solid horizontal line segment, including `+`, the
+    /// configured jump characters, and box-drawing junctions whose horizontal arm is
+    /// single-weight
+    #[inline]
+    pub fn is_solid_h_line(&self, c: char) -> bool {
+        c == '+' || self.jump_chars.contains(c) || self.solid_h_chars.contains(c) || BOX_H_SINGLE_CHARS.contains(c)
+    }
+
+    /// Returns true if the character is a squiggle/wave horizontal line segment, including `+`
+    /// and the configured jump characters
+    #[inline]
+    pub fn is_squiggle_h_line(&self, c: char) -> bool {
+        c == '+' || self.jump_chars.contains(c) || self.squiggle_h_chars.contains(c)
+    }
+
+    /// Returns true if the character is a double horizontal line segment, including `+`, the
+    /// configured jump characters, and box-drawing junctions whose horizontal arm is
+    /// double-weight
+    #[inline]
+    pub fn is_double_h_line(&self, c: char) -> bool {
+        c == '+' || self.jump_chars.contains(c) || self.double_h_chars.contains(c) || BOX_H_DOUBLE_CHARS.contains(c)
+    }
+
+    /// Returns true if the character is any horizontal line type
+    #[inline]
+    pub fn is_any_h_line(&self, c: char) -> bool {
+        self.is_solid_h_line(c) || self.is_squiggle_h_line(c) || self.is_double_h_line(c)
+    }
+
+    /// Returns true if the character is a solid vertical line segment, including `+` and
+    /// box-drawing junctions whose vertical arm is single-weight
+    #[inline]
+    pub fn is_solid_v_line(&self, c: char) -> bool {
+        c == '+' || self.solid_v_chars.contains(c) || BOX_V_SINGLE_CHARS.contains(c)
+    }
+
+    /// Returns true if the character is a double vertical line segment, including `+` and
+    /// box-drawing junctions whose vertical arm is double-weight
+    #[inline]
+    pub fn is_double_v_line(&self, c: char) -> bool {
+        c == '+' || self.double_v_chars.contains(c) || BOX_V_DOUBLE_CHARS.contains(c)
+    }
+
+    /// Returns true if the character is a forward slash diagonal
+    #[inline]
+    pub fn is_solid_d_line(&self, c: char) -> bool {
+        self.forward_diagonal_chars.contains(c)
+    }
+
+    /// Returns true if the character is a backslash diagonal
+    #[inline]
+    pub fn is_solid_b_line(&self, c: char) -> bool {
+        self.back_diagonal_chars.contains(c)
+    }
+
+    /// Returns true if the character is an arrow head
+    #[inline]
+    pub fn is_arrow_head(&self, c: char) -> bool {
+        self.arrow_head_chars.contains(c)
+    }
+
+    /// Returns true if the character is a point/dot decoration
+    #[inline]
+    pub fn is_point(&self, c: char) -> bool {
+        self.point_chars.contains(c)
+    }
+
+    /// Returns true if the character is a jump (bridge) marker
+    #[inline]
+    pub fn is_jump(&self, c: char) -> bool {
+        self.jump_chars.contains(c)
+    }
+
+    /// Returns true if the character is a registered gray fill character
+    #[inline]
+    pub fn is_gray(&self, c: char) -> bool {
+        self.gray_levels.iter().any(|&(ch, _)| ch == c)
+    }
+
+    /// Gray level for fill characters (0-255), or 0 if unregistered
+    pub fn gray_level(&self, c: char) -> u8 {
+        self.gray_levels.iter().find(|&&(ch, _)| ch == c).map(|&(_, level)| level).unwrap_or(0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -256,6 +569,60 @@ mod tests {
         assert!(!is_point('+'));
     }
 
+    #[test]
+    fn test_line_end_marker_detection() {
+        assert!(is_diamond_end('◆'));
+        assert!(is_diamond_end_filled('◆'));
+        assert!(is_diamond_end('◇'));
+        assert!(!is_diamond_end_filled('◇'));
+        assert!(is_circle_end('◉'));
+        assert!(is_circle_end_filled('◉'));
+        assert!(is_circle_end('◯'));
+        assert!(!is_circle_end_filled('◯'));
+        assert!(is_cross_end('#'));
+        // Distinct from the plain point/dot characters
+        assert!(!is_diamond_end('o'));
+        assert!(!is_circle_end('○'));
+    }
+
+    #[test]
+    fn test_box_drawing_corners_and_tees_classify_as_vertex() {
+        assert!(is_vertex('┌'));
+        assert!(is_vertex('┼'));
+        assert!(is_vertex('╬'));
+        assert!(is_vertex('╪'));
+        assert!(!is_vertex('─')); // a straight line segment isn't a junction
+    }
+
+    #[test]
+    fn test_box_drawing_lines_classify_by_weight() {
+        assert!(is_solid_h_line('┬'));
+        assert!(is_solid_v_line('├'));
+        assert!(is_double_h_line('╦'));
+        assert!(is_double_v_line('╠'));
+        // Mixed-weight tee: single vertical arm, double horizontal arms
+        assert!(is_solid_v_line('╤'));
+        assert!(is_double_h_line('╤'));
+        assert!(!is_solid_h_line('╤'));
+    }
+
+    #[test]
+    fn test_box_drawing_directional_vertices() {
+        assert!(is_top_vertex('┌'));
+        assert!(is_bottom_vertex('└'));
+        assert!(is_left_vertex('┌'));
+        assert!(is_right_vertex('┐'));
+        assert!(!is_top_vertex('└'));
+    }
+
+    #[test]
+    fn test_connections() {
+        assert_eq!(connections('┬'), Dirs::DOWN | Dirs::LEFT | Dirs::RIGHT);
+        assert_eq!(connections('└'), Dirs::UP | Dirs::RIGHT);
+        assert_eq!(connections('┼'), Dirs::UP | Dirs::DOWN | Dirs::LEFT | Dirs::RIGHT);
+        assert_eq!(connections('x'), Dirs::NONE);
+    }
+
     #[test]
     fn test_gray_levels() {
         assert_eq!(gray_level('▁'), 64);
@@ -264,4 +631,57 @@ mod tests {
         assert_eq!(gray_level('█'), 255);
         assert_eq!(gray_level('x'), 0);
     }
+
+    #[test]
+    fn test_charset_default_matches_free_functions() {
+        let charset = CharSet::default();
+        assert_eq!(charset.is_solid_h_line('-'), is_solid_h_line('-'));
+        assert_eq!(charset.is_solid_v_line('│'), is_solid_v_line('│'));
+        assert_eq!(charset.is_vertex('┼'), is_vertex('┼'));
+        assert_eq!(charset.is_arrow_head('>'), is_arrow_head('>'));
+        assert_eq!(charset.is_point('●'), is_point('●'));
+        assert_eq!(charset.is_jump('('), is_jump('('));
+        assert_eq!(charset.gray_level('█'), gray_level('█'));
+    }
+
+    #[test]
+    fn test_charset_can_add_custom_point_glyphs() {
+        let mut charset = CharSet::default();
+        charset.point_chars.push_str("•◦");
+        assert!(charset.is_point('•'));
+        assert!(charset.is_point('◦'));
+        assert!(!is_point('•')); // the global default is untouched
+    }
+
+    #[test]
+    fn test_charset_can_disable_squiggle_lines() {
+        let mut charset = CharSet::default();
+        charset.squiggle_h_chars.clear();
+        assert!(!charset.is_squiggle_h_line('~'));
+        assert!(charset.is_solid_h_line('-'));
+    }
+
+    #[test]
+    fn test_charset_custom_vertex_char_is_honored_as_top_and_bottom_vertex() {
+        let mut charset = CharSet::default();
+        charset.vertex_chars.push(':');
+        assert!(charset.is_top_vertex(':'));
+        assert!(charset.is_bottom_vertex(':'));
+        assert!(!is_top_vertex(':')); // the global default is untouched
+        assert!(!is_bottom_vertex(':'));
+
+        // The built-in direction-specific chars still stay on their own end only
+        assert!(charset.is_top_vertex('.'));
+        assert!(!charset.is_bottom_vertex('.'));
+        assert!(charset.is_bottom_vertex('\''));
+        assert!(!charset.is_top_vertex('\''));
+    }
+
+    #[test]
+    fn test_charset_can_register_custom_gray_levels() {
+        let mut charset = CharSet::default();
+        charset.gray_levels.push(('▒', 96));
+        assert!(charset.is_gray('▒'));
+        assert_eq!(charset.gray_level('▒'), 96);
+    }
 }
@@ -3,11 +3,25 @@
 // Many methods are provided for library consumers but not used internally
 #![allow(dead_code)]
 
+use std::io;
+
 use crate::chars::{gray_level, tri_angle};
 use crate::path::{diagonal_angle, Vec2, ASPECT, SCALE};
 
+/// How arrowhead decorations are rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArrowStyle {
+    /// Each arrow is its own `<polygon>` with a `translate/rotate` transform (current behavior)
+    #[default]
+    Polygon,
+    /// Arrows are drawn via a single shared `<marker>` definition referenced from the line paths
+    /// they terminate, via `marker-start`/`marker-end` — more compact on diagrams with many
+    /// arrows, at the cost of needing a renderer that supports SVG markers
+    Marker,
+}
+
 /// Type of decoration
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum DecorationType {
     /// Arrow head (>)
     Arrow,
@@ -27,6 +41,18 @@ pub enum DecorationType {
     Gray(u8),
     /// Triangle decoration
     Triangle,
+    /// Diamond-shaped line terminator (UML aggregation/composition), filled or open, positioned
+    /// and rotated the same way as [`DecorationType::Arrow`]
+    DiamondEnd(bool),
+    /// Circular line terminator, filled or open, positioned and rotated the same way as
+    /// [`DecorationType::Arrow`] (unlike the standalone [`DecorationType::ClosedPoint`] /
+    /// [`DecorationType::OpenPoint`], which sit at a cell's center with no direction)
+    CircleEnd(bool),
+    /// Tee/cross-bar line terminator (e.g. UML "one" multiplicity), a short bar perpendicular to
+    /// the line it terminates
+    CrossEnd,
+    /// One cell of a flood-filled enclosed region (see `crate::fill`), painted the given CSS color
+    Fill(String),
 }
 
 /// A single decoration at a position
@@ -121,11 +147,18 @@ impl Decoration {
         }
     }
 
-    /// Create a gray fill decoration
+    /// Create a gray fill decoration, looking up the shading level for `c` from the default
+    /// [`CharSet`](crate::chars::CharSet) gray-level table
     pub fn gray(x: i32, y: i32, c: char) -> Self {
+        Self::gray_with_level(x, y, gray_level(c))
+    }
+
+    /// Create a gray fill decoration with an already-resolved shading level (0-255), e.g. from a
+    /// caller-supplied [`CharSet`](crate::chars::CharSet)
+    pub fn gray_with_level(x: i32, y: i32, level: u8) -> Self {
         Self {
             pos: Vec2::from_grid(x, y),
-            kind: DecorationType::Gray(gray_level(c)),
+            kind: DecorationType::Gray(level),
             angle: 0.0,
             jump_from: None,
             jump_to: None,
@@ -143,22 +176,98 @@ impl Decoration {
         }
     }
 
-    /// Generate SVG for this decoration
+    /// Create a diamond-shaped line terminator (filled or open), e.g. a UML aggregation/composition
+    /// marker
+    pub fn diamond_end(x: i32, y: i32, angle: f64, filled: bool) -> Self {
+        Self {
+            pos: Vec2::from_grid(x, y),
+            kind: DecorationType::DiamondEnd(filled),
+            angle,
+            jump_from: None,
+            jump_to: None,
+        }
+    }
+
+    /// Create a circular line terminator (filled or open)
+    pub fn circle_end(x: i32, y: i32, angle: f64, filled: bool) -> Self {
+        Self {
+            pos: Vec2::from_grid(x, y),
+            kind: DecorationType::CircleEnd(filled),
+            angle,
+            jump_from: None,
+            jump_to: None,
+        }
+    }
+
+    /// Create a tee/cross-bar line terminator, e.g. a UML "one" multiplicity marker
+    pub fn cross_end(x: i32, y: i32, angle: f64) -> Self {
+        Self {
+            pos: Vec2::from_grid(x, y),
+            kind: DecorationType::CrossEnd,
+            angle,
+            jump_from: None,
+            jump_to: None,
+        }
+    }
+
+    /// Create a flood-fill decoration for one cell of an enclosed region
+    pub fn fill(x: i32, y: i32, color: impl Into<String>) -> Self {
+        Self {
+            pos: Vec2::from_grid(x, y),
+            kind: DecorationType::Fill(color.into()),
+            angle: 0.0,
+            jump_from: None,
+            jump_to: None,
+        }
+    }
+
+    /// Generate SVG for this decoration, with presentation attributes inlined (`fill="var(--aasvg-fill)"`
+    /// etc). See [`Decoration::to_svg_styled`] for a class-based alternative.
     pub fn to_svg(&self) -> String {
-        match self.kind {
-            DecorationType::Arrow => self.arrow_svg(),
-            DecorationType::ClosedPoint => self.closed_point_svg(),
-            DecorationType::OpenPoint => self.open_point_svg(),
-            DecorationType::DottedPoint => self.dotted_point_svg(),
-            DecorationType::ShadedPoint => self.shaded_point_svg(),
-            DecorationType::XorPoint => self.xor_point_svg(),
-            DecorationType::Jump => self.jump_svg(),
-            DecorationType::Gray(level) => self.gray_svg(level),
-            DecorationType::Triangle => self.triangle_svg(),
+        self.to_svg_styled(false)
+    }
+
+    /// Generate SVG for this decoration. When `use_classes` is true, elements whose color is
+    /// fixed by the theme (arrows, points, jumps, triangles) carry a semantic `aasvg-*` class
+    /// instead of inline presentation attributes, so embedders can restyle them from external
+    /// CSS without regenerating the diagram; see [`RenderOptions::with_css_classes`]. Elements
+    /// whose color is per-instance data (`Gray`, `Fill`) keep their inline color either way and
+    /// only gain the class as an additional styling hook.
+    pub fn to_svg_styled(&self, use_classes: bool) -> String {
+        let mut buf = Vec::new();
+        self.write_svg_styled(&mut buf, use_classes)
+            .expect("writing SVG to a Vec<u8> is infallible");
+        String::from_utf8(buf).expect("SVG output is always valid UTF-8")
+    }
+
+    /// Write this decoration's SVG directly to `w`, with presentation attributes inlined, instead
+    /// of building an intermediate `String` first. See [`Decoration::write_svg_styled`].
+    pub fn write_svg(&self, w: &mut impl io::Write) -> io::Result<()> {
+        self.write_svg_styled(w, false)
+    }
+
+    /// Write this decoration's SVG directly to `w`; see [`Decoration::to_svg_styled`] for what
+    /// `use_classes` changes. [`Decoration::to_svg_styled`] is a thin wrapper around this that
+    /// collects the output into a `String` via a `Vec<u8>` buffer.
+    pub fn write_svg_styled(&self, w: &mut impl io::Write, use_classes: bool) -> io::Result<()> {
+        match &self.kind {
+            DecorationType::Arrow => self.write_arrow_svg(w, use_classes),
+            DecorationType::ClosedPoint => self.write_closed_point_svg(w, use_classes),
+            DecorationType::OpenPoint => self.write_open_point_svg(w, use_classes),
+            DecorationType::DottedPoint => self.write_dotted_point_svg(w, use_classes),
+            DecorationType::ShadedPoint => self.write_shaded_point_svg(w, use_classes),
+            DecorationType::XorPoint => self.write_xor_point_svg(w, use_classes),
+            DecorationType::Jump => self.write_jump_svg(w, use_classes),
+            DecorationType::Gray(level) => self.write_gray_svg(w, *level, use_classes),
+            DecorationType::Triangle => self.write_triangle_svg(w, use_classes),
+            DecorationType::DiamondEnd(filled) => self.write_diamond_end_svg(w, *filled, use_classes),
+            DecorationType::CircleEnd(filled) => self.write_circle_end_svg(w, *filled, use_classes),
+            DecorationType::CrossEnd => self.write_cross_end_svg(w, use_classes),
+            DecorationType::Fill(color) => self.write_fill_svg(w, color, use_classes),
         }
     }
 
-    fn arrow_svg(&self) -> String {
+    fn write_arrow_svg(&self, w: &mut impl io::Write, use_classes: bool) -> io::Result<()> {
         let cx = self.pos.x;
         let cy = self.pos.y;
 
@@ -169,112 +278,274 @@ impl Decoration {
         let back_up_y = -3.0;
         let back_down_y = 3.0;
 
-        format!(
-            "<polygon points=\"{},{} {},{} {},{}\" fill=\"var(--aasvg-fill)\" transform=\"translate({},{}) rotate({})\"/>\n",
+        let fill_attr = if use_classes {
+            " class=\"aasvg-arrow\""
+        } else {
+            " fill=\"var(--aasvg-fill)\""
+        };
+
+        writeln!(
+            w,
+            "<polygon points=\"{},{} {},{} {},{}\"{} transform=\"translate({},{}) rotate({})\"/>",
             tip_x, tip_y,
             back_x, back_up_y,
             back_x, back_down_y,
+            fill_attr,
             cx, cy,
             self.angle
         )
     }
 
-    fn closed_point_svg(&self) -> String {
+    fn write_closed_point_svg(&self, w: &mut impl io::Write, use_classes: bool) -> io::Result<()> {
         let r = SCALE - 2.0;
-        format!(
-            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"var(--aasvg-fill)\"/>\n",
-            self.pos.x, self.pos.y, r
+        let attrs = if use_classes {
+            " class=\"aasvg-point-closed\""
+        } else {
+            " fill=\"var(--aasvg-fill)\""
+        };
+        writeln!(
+            w,
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\"{}/>",
+            self.pos.x, self.pos.y, r, attrs
         )
     }
 
-    fn open_point_svg(&self) -> String {
+    fn write_open_point_svg(&self, w: &mut impl io::Write, use_classes: bool) -> io::Result<()> {
         let r = SCALE - 2.0;
-        format!(
-            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"var(--aasvg-bg)\" stroke=\"var(--aasvg-stroke)\"/>\n",
-            self.pos.x, self.pos.y, r
+        let attrs = if use_classes {
+            " class=\"aasvg-point-open\""
+        } else {
+            " fill=\"var(--aasvg-bg)\" stroke=\"var(--aasvg-stroke)\""
+        };
+        writeln!(
+            w,
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\"{}/>",
+            self.pos.x, self.pos.y, r, attrs
         )
     }
 
-    fn dotted_point_svg(&self) -> String {
+    fn write_dotted_point_svg(&self, w: &mut impl io::Write, use_classes: bool) -> io::Result<()> {
         let r = SCALE - 2.0;
-        format!(
-            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"var(--aasvg-bg)\" stroke=\"var(--aasvg-stroke)\" stroke-dasharray=\"2,2\"/>\n",
-            self.pos.x, self.pos.y, r
+        let attrs = if use_classes {
+            " class=\"aasvg-point-dotted\""
+        } else {
+            " fill=\"var(--aasvg-bg)\" stroke=\"var(--aasvg-stroke)\""
+        };
+        writeln!(
+            w,
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\"{} stroke-dasharray=\"2,2\"/>",
+            self.pos.x, self.pos.y, r, attrs
         )
     }
 
-    fn shaded_point_svg(&self) -> String {
+    fn write_shaded_point_svg(&self, w: &mut impl io::Write, use_classes: bool) -> io::Result<()> {
         let r = SCALE - 2.0;
         // Shaded points use a gray fill that should work in both modes
-        format!(
-            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"#888\" stroke=\"var(--aasvg-stroke)\"/>\n",
-            self.pos.x, self.pos.y, r
+        let attrs = if use_classes {
+            " class=\"aasvg-point-shaded\""
+        } else {
+            " fill=\"#888\" stroke=\"var(--aasvg-stroke)\""
+        };
+        writeln!(
+            w,
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\"{}/>",
+            self.pos.x, self.pos.y, r, attrs
         )
     }
 
-    fn xor_point_svg(&self) -> String {
+    fn write_xor_point_svg(&self, w: &mut impl io::Write, use_classes: bool) -> io::Result<()> {
         let r = SCALE - 2.0;
         let cx = self.pos.x;
         let cy = self.pos.y;
 
-        format!(
-            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"var(--aasvg-bg)\" stroke=\"var(--aasvg-stroke)\"/>\n\
-             <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"var(--aasvg-stroke)\"/>\n\
-             <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"var(--aasvg-stroke)\"/>\n",
-            cx, cy, r,
-            cx - r, cy, cx + r, cy,  // Horizontal line through center
-            cx, cy - r, cx, cy + r   // Vertical line through center
+        let (circle_attrs, line_attrs) = if use_classes {
+            (" class=\"aasvg-point-xor\"", " class=\"aasvg-point-xor-line\"")
+        } else {
+            (
+                " fill=\"var(--aasvg-bg)\" stroke=\"var(--aasvg-stroke)\"",
+                " stroke=\"var(--aasvg-stroke)\"",
+            )
+        };
+
+        writeln!(
+            w,
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\"{}/>\n\
+             <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"{}/>\n\
+             <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"{}/>",
+            cx, cy, r, circle_attrs,
+            cx - r, cy, cx + r, cy, line_attrs,  // Horizontal line through center
+            cx, cy - r, cx, cy + r, line_attrs   // Vertical line through center
         )
     }
 
-    fn jump_svg(&self) -> String {
-        if let (Some(from), Some(to)) = (self.jump_from, self.jump_to) {
-            let mid_y = (from.y + to.y) / 2.0;
-            let cx1 = from.x + SCALE;
-            let cx2 = to.x + SCALE;
+    fn write_jump_svg(&self, w: &mut impl io::Write, use_classes: bool) -> io::Result<()> {
+        let (Some(from), Some(to)) = (self.jump_from, self.jump_to) else {
+            return Ok(());
+        };
+        let mid_y = (from.y + to.y) / 2.0;
+        let cx1 = from.x + SCALE;
+        let cx2 = to.x + SCALE;
 
-            format!(
-                "<path d=\"M {},{} C {},{} {},{} {},{}\" fill=\"none\" stroke=\"var(--aasvg-bg)\" stroke-width=\"3\"/>\n\
-                 <path d=\"M {},{} C {},{} {},{} {},{}\" fill=\"none\" stroke=\"var(--aasvg-stroke)\"/>\n",
-                from.x, from.y, cx1, mid_y, cx2, mid_y, to.x, to.y,
-                from.x, from.y, cx1, mid_y, cx2, mid_y, to.x, to.y
-            )
+        let (bg_attrs, fg_attrs) = if use_classes {
+            (" class=\"aasvg-jump-bg\"", " class=\"aasvg-jump\"")
         } else {
-            String::new()
-        }
+            (
+                " fill=\"none\" stroke=\"var(--aasvg-bg)\" stroke-width=\"3\"",
+                " fill=\"none\" stroke=\"var(--aasvg-stroke)\"",
+            )
+        };
+
+        writeln!(
+            w,
+            "<path d=\"M {},{} C {},{} {},{} {},{}\"{}/>\n\
+             <path d=\"M {},{} C {},{} {},{} {},{}\"{}/>",
+            from.x, from.y, cx1, mid_y, cx2, mid_y, to.x, to.y, bg_attrs,
+            from.x, from.y, cx1, mid_y, cx2, mid_y, to.x, to.y, fg_attrs
+        )
     }
 
-    fn gray_svg(&self, level: u8) -> String {
-        // Gray fill rectangle
+    fn write_gray_svg(&self, w: &mut impl io::Write, level: u8, use_classes: bool) -> io::Result<()> {
+        // Gray fill rectangle. The shade is per-character data, not a theme color, so it stays
+        // inline even in class mode; the class is an additional hook (e.g. to restyle opacity).
         let x = self.pos.x - SCALE / 2.0;
         let y = self.pos.y - SCALE * ASPECT / 2.0;
-        let w = SCALE;
+        let wd = SCALE;
         let h = SCALE * ASPECT;
+        let class_attr = if use_classes { " class=\"aasvg-gray\"" } else { "" };
+
+        writeln!(
+            w,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"rgb({},{},{})\"{}/>",
+            x, y, wd, h, level, level, level, class_attr
+        )
+    }
 
-        format!(
-            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"rgb({},{},{})\"/>\n",
-            x, y, w, h, level, level, level
+    fn write_fill_svg(&self, w: &mut impl io::Write, color: &str, use_classes: bool) -> io::Result<()> {
+        // Full cell, unlike `write_gray_svg`'s smaller swatch: this is meant to read as a continuous
+        // wash across every cell of the enclosed region, not a per-character mark. The color
+        // comes from the region's fill marker, not the theme, so it stays inline in class mode
+        // too; the class is an additional hook.
+        let x = self.pos.x - SCALE;
+        let y = self.pos.y - SCALE * ASPECT;
+        let wd = SCALE * 2.0;
+        let h = SCALE * ASPECT * 2.0;
+        let class_attr = if use_classes { " class=\"aasvg-region-fill\"" } else { "" };
+
+        writeln!(
+            w,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"{}/>",
+            x, y, wd, h, color, class_attr
         )
     }
 
-    fn triangle_svg(&self) -> String {
+    fn write_triangle_svg(&self, w: &mut impl io::Write, use_classes: bool) -> io::Result<()> {
         let cx = self.pos.x;
         let cy = self.pos.y;
         let s = SCALE / 2.0;
         let h = SCALE * ASPECT / 2.0;
 
+        let fill_attr = if use_classes {
+            " class=\"aasvg-triangle\""
+        } else {
+            " fill=\"var(--aasvg-fill)\""
+        };
+
         // Triangle pointing right, then rotated
-        format!(
-            "<polygon points=\"{},{} {},{} {},{}\" fill=\"var(--aasvg-fill)\" transform=\"translate({},{}) rotate({})\"/>\n",
+        writeln!(
+            w,
+            "<polygon points=\"{},{} {},{} {},{}\"{} transform=\"translate({},{}) rotate({})\"/>",
             s, 0.0,    // Right point
             -s, -h,    // Top-left
             -s, h,     // Bottom-left
+            fill_attr,
+            cx, cy,
+            self.angle
+        )
+    }
+
+    fn write_diamond_end_svg(&self, w: &mut impl io::Write, filled: bool, use_classes: bool) -> io::Result<()> {
+        let cx = self.pos.x;
+        let cy = self.pos.y;
+
+        // Elongated rhombus along the line direction: far tip, two side points, near back point
+        let tip_x = SCALE;
+        let side_x = SCALE / 2.0;
+        let side_y = SCALE * ASPECT / 2.0;
+
+        let attrs = match (use_classes, filled) {
+            (true, true) => " class=\"aasvg-diamond-filled\"",
+            (true, false) => " class=\"aasvg-diamond-open\"",
+            (false, true) => " fill=\"var(--aasvg-fill)\"",
+            (false, false) => " fill=\"var(--aasvg-bg)\" stroke=\"var(--aasvg-stroke)\"",
+        };
+
+        writeln!(
+            w,
+            "<polygon points=\"{},{} {},{} {},{} {},{}\"{} transform=\"translate({},{}) rotate({})\"/>",
+            tip_x, 0.0,
+            side_x, -side_y,
+            0.0, 0.0,
+            side_x, side_y,
+            attrs,
             cx, cy,
             self.angle
         )
     }
+
+    fn write_circle_end_svg(&self, w: &mut impl io::Write, filled: bool, use_classes: bool) -> io::Result<()> {
+        let cx = self.pos.x;
+        let cy = self.pos.y;
+        let r = SCALE / 2.0;
+        // Center of the circle sits `SCALE` (one diameter) forward of the decoration's position,
+        // same as the arrow's tip offset, so swapping `ArrowStyle`-like terminators doesn't change
+        // where the line stops
+        let offset = SCALE;
+
+        let attrs = match (use_classes, filled) {
+            (true, true) => " class=\"aasvg-circle-end-filled\"",
+            (true, false) => " class=\"aasvg-circle-end-open\"",
+            (false, true) => " fill=\"var(--aasvg-fill)\"",
+            (false, false) => " fill=\"var(--aasvg-bg)\" stroke=\"var(--aasvg-stroke)\"",
+        };
+
+        writeln!(
+            w,
+            "<circle cx=\"{}\" cy=\"0\" r=\"{}\"{} transform=\"translate({},{}) rotate({})\"/>",
+            offset, r, attrs, cx, cy, self.angle
+        )
+    }
+
+    fn write_cross_end_svg(&self, w: &mut impl io::Write, use_classes: bool) -> io::Result<()> {
+        let cx = self.pos.x;
+        let cy = self.pos.y;
+        let bar_x = SCALE * 0.75;
+        let bar_y = SCALE * ASPECT / 2.0;
+
+        let attrs = if use_classes {
+            " class=\"aasvg-cross-end\""
+        } else {
+            " stroke=\"var(--aasvg-stroke)\""
+        };
+
+        writeln!(
+            w,
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"{} transform=\"translate({},{}) rotate({})\"/>",
+            bar_x, -bar_y, bar_x, bar_y, attrs, cx, cy, self.angle
+        )
+    }
 }
 
+/// `<defs>` block for [`ArrowStyle::Marker`]: one reusable arrowhead, shaped to match
+/// `Decoration::write_arrow_svg`'s own polygon so switching styles doesn't change how an arrow looks.
+/// `orient="auto"` has the renderer rotate it to the path's tangent at the point it's attached to,
+/// so no per-arrow rotation is computed here the way `write_arrow_svg`'s `angle` is.
+pub const ARROW_MARKER_DEFS: &str = r#"<defs>
+<marker id="aasvg-arrow" viewBox="-4 -3 12 6" refX="8" refY="0" markerWidth="8" markerHeight="8" orient="auto">
+<polygon points="8,0 -4,-3 -4,3" fill="var(--aasvg-fill)"/>
+</marker>
+</defs>
+"#;
+
 /// Angle for right-pointing arrow
 pub const ARROW_RIGHT: f64 = 0.0;
 /// Angle for down-pointing arrow
@@ -330,16 +601,88 @@ impl DecorationSet {
         self.decorations.is_empty()
     }
 
-    /// Generate SVG for all decorations
+    /// Generate SVG for all decorations, with presentation attributes inlined
     pub fn to_svg(&self) -> String {
-        let mut result = String::new();
+        self.to_svg_styled(false)
+    }
+
+    /// Generate SVG for all decorations; see [`Decoration::to_svg_styled`]
+    pub fn to_svg_styled(&self, use_classes: bool) -> String {
+        let mut buf = Vec::new();
+        self.write_svg_styled(&mut buf, use_classes)
+            .expect("writing SVG to a Vec<u8> is infallible");
+        String::from_utf8(buf).expect("SVG output is always valid UTF-8")
+    }
+
+    /// Generate SVG for all decorations except the ones flagged in `skip` (indexed in the same
+    /// order as [`DecorationSet::iter`]), used in [`ArrowStyle::Marker`] mode to leave out an
+    /// `Arrow` decoration once it's been attached to its path as a `marker-start`/`marker-end`
+    /// instead
+    pub fn to_svg_skipping(&self, skip: &[bool]) -> String {
+        self.to_svg_skipping_styled(skip, false)
+    }
+
+    /// Combines [`DecorationSet::to_svg_skipping`] and [`DecorationSet::to_svg_styled`]
+    pub fn to_svg_skipping_styled(&self, skip: &[bool], use_classes: bool) -> String {
+        let mut buf = Vec::new();
+        self.write_svg_skipping_styled(skip, &mut buf, use_classes)
+            .expect("writing SVG to a Vec<u8> is infallible");
+        String::from_utf8(buf).expect("SVG output is always valid UTF-8")
+    }
+
+    /// Write SVG for all decorations directly to `w`, with presentation attributes inlined,
+    /// instead of building one large intermediate `String` first — useful for diagrams with many
+    /// decorations, streamed straight to a file or socket via [`crate::render_to_writer`]
+    pub fn write_svg(&self, w: &mut impl io::Write) -> io::Result<()> {
+        self.write_svg_styled(w, false)
+    }
+
+    /// Write SVG for all decorations directly to `w`; see [`Decoration::write_svg_styled`] for
+    /// what `use_classes` changes. [`DecorationSet::to_svg_styled`] is a thin wrapper around this.
+    pub fn write_svg_styled(&self, w: &mut impl io::Write, use_classes: bool) -> io::Result<()> {
         for decoration in &self.decorations {
-            result.push_str(&decoration.to_svg());
+            decoration.write_svg_styled(w, use_classes)?;
         }
-        result
+        Ok(())
+    }
+
+    /// Write SVG for all decorations except the ones flagged in `skip`, directly to `w`; see
+    /// [`DecorationSet::to_svg_skipping`]
+    pub fn write_svg_skipping(&self, skip: &[bool], w: &mut impl io::Write) -> io::Result<()> {
+        self.write_svg_skipping_styled(skip, w, false)
+    }
+
+    /// Combines [`DecorationSet::write_svg_skipping`] and [`DecorationSet::write_svg_styled`]
+    pub fn write_svg_skipping_styled(
+        &self,
+        skip: &[bool],
+        w: &mut impl io::Write,
+        use_classes: bool,
+    ) -> io::Result<()> {
+        for (decoration, &skipped) in self.decorations.iter().zip(skip) {
+            if !skipped {
+                decoration.write_svg_styled(w, use_classes)?;
+            }
+        }
+        Ok(())
     }
 }
 
+/// Default stylesheet rules mapping the `aasvg-*` classes emitted in
+/// [`RenderOptions::with_css_classes`] mode to the same light/dark CSS variables the inline
+/// presentation attributes would otherwise use, so turning the mode on doesn't change how a
+/// diagram looks by default — only how it can be restyled.
+pub const CSS_CLASSES_STYLESHEET: &str = r#"<style>
+.aasvg-arrow, .aasvg-point-closed, .aasvg-triangle, .aasvg-diamond-filled, .aasvg-circle-end-filled { fill: var(--aasvg-fill); }
+.aasvg-point-open, .aasvg-point-dotted, .aasvg-point-xor, .aasvg-diamond-open, .aasvg-circle-end-open { fill: var(--aasvg-bg); stroke: var(--aasvg-stroke); }
+.aasvg-point-shaded { fill: #888; stroke: var(--aasvg-stroke); }
+.aasvg-point-xor-line { stroke: var(--aasvg-stroke); }
+.aasvg-jump-bg { fill: none; stroke: var(--aasvg-bg); stroke-width: 3; }
+.aasvg-jump { fill: none; stroke: var(--aasvg-stroke); }
+.aasvg-cross-end { stroke: var(--aasvg-stroke); }
+</style>
+"#;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -375,4 +718,29 @@ mod tests {
         assert!(svg.contains("polygon"));
         assert!(svg.contains("var(--aasvg-fill)"));
     }
+
+    #[test]
+    fn test_diamond_end_creation() {
+        let filled = Decoration::diamond_end(0, 0, ARROW_RIGHT, true);
+        assert_eq!(filled.kind, DecorationType::DiamondEnd(true));
+
+        let open = Decoration::diamond_end(0, 0, ARROW_RIGHT, false);
+        assert_eq!(open.kind, DecorationType::DiamondEnd(false));
+    }
+
+    #[test]
+    fn test_circle_end_svg_is_offset_and_rotated_like_arrow() {
+        let circle = Decoration::circle_end(0, 0, ARROW_DOWN, true);
+        let svg = circle.to_svg();
+        assert!(svg.contains("<circle"));
+        assert!(svg.contains(&format!("cx=\"{}\"", SCALE)));
+        assert!(svg.contains(&format!("rotate({})", ARROW_DOWN)));
+    }
+
+    #[test]
+    fn test_cross_end_svg_output() {
+        let cross = Decoration::cross_end(0, 0, ARROW_UP);
+        let svg = cross.to_svg();
+        assert!(svg.contains("<line"));
+    }
 }
@@ -0,0 +1,249 @@
+//! Region fill.
+//!
+//! `find_gray_fills` (see [`crate::finder`]) only paints a single cell carrying a shade
+//! character; it has no notion of the open whitespace *inside* a drawn box. This pass looks for
+//! connected blank cells fully enclosed by the paths and shapes already found and emits a filled
+//! [`Decoration`] for each one, using a winding-number test rather than simple containment so a
+//! ring between two nested boxes can be told apart from the hole inside the inner one.
+
+#![allow(dead_code)]
+
+use crate::decoration::{Decoration, DecorationSet};
+use crate::grid::Grid;
+use crate::path::{PathSet, Vec2};
+use crate::shape::ShapeSet;
+
+/// How a region's winding number is interpreted to decide whether it counts as "inside"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillRule {
+    /// Inside whenever the winding number is non-zero: nested same-direction loops stay filled
+    /// all the way through to the center
+    #[default]
+    NonZero,
+    /// Inside whenever the winding number is odd: nested loops alternate filled/unfilled rings
+    EvenOdd,
+}
+
+/// Characters that mark the fill color for the region they sit inside. Only recognized when
+/// blank to both sides (so a lone `r` reads as a marker, but the `r` in `Error` doesn't).
+const FILL_MARKERS: &[(char, &str)] = &[
+    ('r', "red"),
+    ('g', "green"),
+    ('b', "blue"),
+    ('y', "yellow"),
+    ('c', "cyan"),
+    ('m', "magenta"),
+];
+
+fn marker_color(c: char) -> Option<&'static str> {
+    FILL_MARKERS.iter().find(|(m, _)| *m == c).map(|(_, color)| *color)
+}
+
+/// Collect every boundary edge, as SVG-space line segments, contributed by a found path's
+/// segments or by an endorsed shape's (already-closed) outline
+fn boundary_segments(paths: &PathSet, shapes: &ShapeSet) -> Vec<(Vec2, Vec2)> {
+    let mut segments = Vec::new();
+
+    for path in paths.iter() {
+        let mut from = path.start;
+        for seg in &path.segments {
+            segments.push((from, seg.to));
+            from = seg.to;
+        }
+    }
+
+    for shape in shapes.iter() {
+        let n = shape.points.len();
+        for i in 0..n {
+            segments.push((shape.points[i], shape.points[(i + 1) % n]));
+        }
+    }
+
+    segments
+}
+
+/// Which side of the directed edge `a -> b` the point `p` falls on (positive: left, negative: right)
+fn is_left(a: Vec2, b: Vec2, p: Vec2) -> f64 {
+    (b.x - a.x) * (p.y - a.y) - (p.x - a.x) * (b.y - a.y)
+}
+
+/// Winding number of `point` around the closed boundary formed by `segments`.
+///
+/// This is Dan Sunday's winding-number algorithm rather than literal ray casting: each edge that
+/// crosses the horizontal line through `point` contributes +1 or -1 depending on which way it
+/// crosses. The half-open `a.y <= point.y < b.y` test means an edge that only touches the line at
+/// a shared vertex is counted on exactly one of the two edges meeting there, never both.
+fn winding_number(point: Vec2, segments: &[(Vec2, Vec2)]) -> i32 {
+    let mut wn = 0;
+    for &(a, b) in segments {
+        if a.y <= point.y {
+            if b.y > point.y && is_left(a, b, point) > 0.0 {
+                wn += 1;
+            }
+        } else if b.y <= point.y && is_left(a, b, point) < 0.0 {
+            wn -= 1;
+        }
+    }
+    wn
+}
+
+fn is_enclosed(point: Vec2, segments: &[(Vec2, Vec2)], rule: FillRule) -> bool {
+    let wn = winding_number(point, segments);
+    match rule {
+        FillRule::NonZero => wn != 0,
+        FillRule::EvenOdd => wn % 2 != 0,
+    }
+}
+
+/// Look for a single marker character within the region's bounding box, blank to its left and
+/// right so it reads as a standalone mark rather than part of a word, and resolve it to a CSS
+/// color
+fn find_marker(grid: &Grid, min_x: i32, max_x: i32, min_y: i32, max_y: i32) -> Option<(i32, i32, &'static str)> {
+    for y in min_y..=max_y {
+        for x in min_x..=max_x {
+            let c = grid.get(x, y);
+            if c == ' ' || grid.is_used(x, y) {
+                continue;
+            }
+            let isolated = grid.get(x - 1, y) == ' ' && grid.get(x + 1, y) == ' ';
+            if isolated {
+                if let Some(color) = marker_color(c) {
+                    return Some((x, y, color));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Find connected regions of blank, unclaimed cells fully enclosed by the paths/shapes already
+/// found, and emit a filled decoration for each one that carries a recognized fill marker.
+///
+/// A region that touches the grid's outer border is left alone even if the winding test would
+/// call it "inside": an open boundary with a gap can otherwise leak the fill across the whole
+/// canvas, and nothing genuinely enclosed ever touches the border.
+pub fn find_fills(
+    grid: &mut Grid,
+    paths: &PathSet,
+    shapes: &ShapeSet,
+    decorations: &mut DecorationSet,
+    rule: FillRule,
+) {
+    let segments = boundary_segments(paths, shapes);
+    if segments.is_empty() {
+        return;
+    }
+
+    let width = grid.width as i32;
+    let height = grid.height as i32;
+    let mut visited = vec![vec![false; grid.width]; grid.height];
+
+    for start_y in 0..height {
+        for start_x in 0..width {
+            if visited[start_y as usize][start_x as usize] {
+                continue;
+            }
+            if grid.get(start_x, start_y) != ' ' || grid.is_used(start_x, start_y) {
+                visited[start_y as usize][start_x as usize] = true;
+                continue;
+            }
+
+            let mut stack = vec![(start_x, start_y)];
+            let mut cells = Vec::new();
+            let mut escaped = false;
+            visited[start_y as usize][start_x as usize] = true;
+
+            while let Some((x, y)) = stack.pop() {
+                cells.push((x, y));
+                if x == 0 || y == 0 || x == width - 1 || y == height - 1 {
+                    escaped = true;
+                }
+                for (nx, ny) in [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)] {
+                    if nx < 0 || ny < 0 || nx >= width || ny >= height {
+                        continue;
+                    }
+                    let (ux, uy) = (nx as usize, ny as usize);
+                    if visited[uy][ux] || grid.get(nx, ny) != ' ' || grid.is_used(nx, ny) {
+                        continue;
+                    }
+                    visited[uy][ux] = true;
+                    stack.push((nx, ny));
+                }
+            }
+
+            if escaped {
+                continue;
+            }
+
+            let (rep_x, rep_y) = cells[0];
+            if !is_enclosed(Vec2::from_grid(rep_x, rep_y), &segments, rule) {
+                continue;
+            }
+
+            let (min_x, max_x, min_y, max_y) = cells.iter().fold(
+                (i32::MAX, i32::MIN, i32::MAX, i32::MIN),
+                |(lx, hx, ly, hy), &(x, y)| (lx.min(x), hx.max(x), ly.min(y), hy.max(y)),
+            );
+
+            // A lone marker breaks the flood-fill's own connectivity (it isn't blank), so it can
+            // end up just outside the blank region's bounding box; widen the search by one cell
+            // to still catch it.
+            let Some((mx, my, color)) = find_marker(grid, min_x - 1, max_x + 1, min_y - 1, max_y + 1) else {
+                continue;
+            };
+            grid.set_used(mx, my);
+
+            for (x, y) in cells {
+                decorations.insert(Decoration::fill(x, y, color));
+                grid.set_used(x, y);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finder::find_paths;
+    use crate::shape;
+
+    #[test]
+    fn test_winding_number_inside_and_outside() {
+        let segments = vec![
+            (Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0)),
+            (Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0)),
+            (Vec2::new(10.0, 10.0), Vec2::new(0.0, 10.0)),
+            (Vec2::new(0.0, 10.0), Vec2::new(0.0, 0.0)),
+        ];
+        assert!(is_enclosed(Vec2::new(5.0, 5.0), &segments, FillRule::NonZero));
+        assert!(!is_enclosed(Vec2::new(20.0, 20.0), &segments, FillRule::NonZero));
+    }
+
+    #[test]
+    fn test_find_fills_marks_enclosed_region_with_marker() {
+        let mut grid = Grid::new("+----+\n| r  |\n+----+");
+        let mut paths = PathSet::new();
+        let mut shapes = ShapeSet::new();
+        let mut decorations = DecorationSet::new();
+
+        find_paths(&mut grid, &mut paths, 0.0);
+        shape::endorse(&mut paths, &mut shapes);
+        find_fills(&mut grid, &paths, &shapes, &mut decorations, FillRule::NonZero);
+
+        assert!(!decorations.is_empty());
+    }
+
+    #[test]
+    fn test_find_fills_skips_region_without_marker() {
+        let mut grid = Grid::new("+----+\n|    |\n+----+");
+        let mut paths = PathSet::new();
+        let mut shapes = ShapeSet::new();
+        let mut decorations = DecorationSet::new();
+
+        find_paths(&mut grid, &mut paths, 0.0);
+        shape::endorse(&mut paths, &mut shapes);
+        find_fills(&mut grid, &paths, &shapes, &mut decorations, FillRule::NonZero);
+
+        assert!(decorations.is_empty());
+    }
+}
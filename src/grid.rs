@@ -8,49 +8,230 @@
 
 use crate::chars::*;
 
+/// Placeholder occupying the second column of a double-width character, so grid coordinates
+/// still map 1:1 to visual columns. No scanner ever matches it (it isn't any recognized
+/// line/vertex/arrow character), so it behaves like blank space for path finding; text
+/// extraction explicitly skips over it instead of treating it as its own token.
+pub const WIDE_CONTINUATION: char = '\u{E003}';
+
+/// Visual width of a character: 2 for wide (CJK/fullwidth/etc.) glyphs, 0 for combining marks
+/// (which attach to the cell before them instead of taking one of their own), 1 otherwise.
+///
+/// This is a small hand-rolled approximation of East Asian Width rather than a full Unicode
+/// table, covering the common CJK/Hangul/fullwidth ranges `svgbob`'s own grid handles; there's no
+/// dependency manifest here to pull in the `unicode-width` crate.
+fn char_width(c: char) -> usize {
+    let cp = c as u32;
+    let is_combining = matches!(cp,
+        0x0300..=0x036F | 0x1AB0..=0x1AFF | 0x1DC0..=0x1DFF | 0x20D0..=0x20FF | 0xFE20..=0xFE2F
+    );
+    let is_wide = matches!(cp,
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x20000..=0x3FFFD
+    );
+
+    if is_combining {
+        0
+    } else if is_wide {
+        2
+    } else {
+        1
+    }
+}
+
+/// Expand a line into one grid cell per visual column, where each cell holds a whole grapheme
+/// cluster rather than a single `char`: a wide character gets a real cell plus a
+/// [`WIDE_CONTINUATION`] placeholder, and a combining mark is appended onto the cluster string of
+/// the cell before it instead of taking one of its own. This is a hand-rolled approximation of
+/// `unicode-segmentation`'s `graphemes(true)` (combining-mark ranges only, not full grapheme
+/// cluster break rules), since there's no dependency manifest here to pull in that crate.
+fn expand_line(line: &str) -> Vec<String> {
+    let mut row: Vec<String> = Vec::new();
+    for c in line.chars() {
+        match char_width(c) {
+            0 if !row.is_empty() => {
+                row.last_mut().unwrap().push(c);
+            }
+            2 => {
+                row.push(c.to_string());
+                row.push(WIDE_CONTINUATION.to_string());
+            }
+            _ => row.push(c.to_string()),
+        }
+    }
+    row
+}
+
+/// Sum of each character's visual width, i.e. how many grid columns `line` will expand to
+fn visual_width(line: &str) -> usize {
+    line.chars().map(char_width).sum()
+}
+
+/// Delimiters that open/close a literal-text escape region: double quotes or backticks
+const LITERAL_DELIMITERS: [&str; 2] = ["\"", "`"];
+
+/// Find quote- or backtick-delimited runs in `row` — an explicit escape an author can use to force
+/// a span to render as plain text instead of being read as line/point characters (e.g. a label
+/// like `"v2"` whose bare `v` would otherwise look like a down arrow). Blanks out the region's
+/// cells (delimiters included) so no finder mistakes their content for line/point characters, and
+/// records each run's start column and literal text in `literals`. Returns the inclusive
+/// `(start, end)` column range of every span found, so the caller can mark those cells used and
+/// literal.
+fn extract_literal_texts(row: &mut [String], y: i32, literals: &mut Vec<(i32, i32, String)>) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut x = 0;
+    while x < row.len() {
+        if let Some(delim) = LITERAL_DELIMITERS.iter().find(|&&d| row[x] == d) {
+            if let Some(offset) = row[x + 1..].iter().position(|c| c == delim) {
+                let end = x + 1 + offset;
+                let text: String = row[x + 1..end].iter().map(String::as_str).collect();
+                literals.push((x as i32 + 1, y, text));
+                spans.push((x, end));
+                for cell in &mut row[x..=end] {
+                    *cell = " ".to_string();
+                }
+                x = end + 1;
+                continue;
+            }
+        }
+        x += 1;
+    }
+    spans
+}
+
 /// 2D grid of characters with "used" tracking
 pub struct Grid {
-    /// Characters in the grid (row-major order)
-    chars: Vec<Vec<char>>,
+    /// Grapheme clusters in the grid (row-major order); usually one `char` long, but a base
+    /// scalar with combining marks attached is kept as a single multi-`char` `String` cell
+    chars: Vec<Vec<String>>,
     /// Track which cells have been consumed
     used: Vec<Vec<bool>>,
+    /// Track which cells fall inside a quote/backtick-delimited literal-text escape region (see
+    /// [`Grid::is_literal`]); those cells are blanked and marked used during construction, so no
+    /// path or decoration finder ever classifies the escaped characters
+    literal: Vec<Vec<bool>>,
     /// Grid width (longest line)
     pub width: usize,
     /// Grid height (number of lines)
     pub height: usize,
+    /// Quoted literal text spans found during construction: (start column, row, text)
+    literals: Vec<(i32, i32, String)>,
+    /// Character classification table consulted by every `is_*_at` detection method
+    charset: CharSet,
+    /// Legend entries parsed from a trailing `# key = {css-properties}` block, in the order they
+    /// appeared (see [`Grid::styles`])
+    styles: Vec<(String, String)>,
 }
 
 impl Grid {
-    /// Create a grid from a diagram string
+    /// Create a grid from a diagram string, classifying characters with [`CharSet::default`]
     pub fn new(input: &str) -> Self {
-        let input = preprocess(input);
+        Self::with_charset(input, CharSet::default())
+    }
+
+    /// Create a grid from a diagram string, classifying characters with a caller-supplied
+    /// [`CharSet`] instead of the default one. Lets a consumer adapt the parser to their own
+    /// diagram conventions (extra point glyphs, custom shading levels, a narrower line-character
+    /// vocabulary, etc.) without forking the crate.
+    pub fn with_charset(input: &str, charset: CharSet) -> Self {
+        let (input, styles) = extract_legend(input);
+        let input = preprocess(&input);
         let lines: Vec<&str> = input.lines().collect();
 
         let height = lines.len();
-        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut rows: Vec<Vec<String>> = lines.iter().map(|l| expand_line(l)).collect();
+        let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        for row in &mut rows {
+            while row.len() < width {
+                row.push(" ".to_string());
+            }
+        }
 
-        let mut chars = Vec::with_capacity(height);
+        let mut literals = Vec::new();
         let mut used = Vec::with_capacity(height);
-
-        for line in &lines {
-            let mut row: Vec<char> = line.chars().collect();
-            // Pad to width
-            while row.len() < width {
-                row.push(' ');
+        let mut literal = Vec::with_capacity(height);
+
+        for (y, row) in rows.iter_mut().enumerate() {
+            let mut row_used = vec![false; width];
+            let mut row_literal = vec![false; width];
+            for (start, end) in extract_literal_texts(row, y as i32, &mut literals) {
+                for flag in &mut row_used[start..=end] {
+                    *flag = true;
+                }
+                for flag in &mut row_literal[start..=end] {
+                    *flag = true;
+                }
             }
-            chars.push(row);
-            used.push(vec![false; width]);
+            used.push(row_used);
+            literal.push(row_literal);
         }
 
         Self {
-            chars,
+            chars: rows,
             used,
+            literal,
             width,
             height,
+            literals,
+            charset,
+            styles,
         }
     }
 
-    /// Get the character at position (x, y), or space if out of bounds
+    /// The character classification table this grid was constructed with (see
+    /// [`Grid::with_charset`])
+    pub fn charset(&self) -> &CharSet {
+        &self.charset
+    }
+
+    /// Legend entries (key, raw CSS properties) parsed from a trailing `# key = {css-properties}`
+    /// block, in the order they appeared. Empty if the diagram didn't end with one. See
+    /// [`Grid::style_key_at`] to look up which legend key (if any) labels a given cell's character.
+    pub fn styles(&self) -> &[(String, String)] {
+        &self.styles
+    }
+
+    /// The legend key registered for the character at `(x, y)`, if any, with its matching CSS
+    /// properties from [`Grid::styles`]. A legend maps single marker characters to styles (e.g.
+    /// `# r = {stroke:red}` labels every `r` in the diagram body), so this compares the cell's
+    /// character against each key as a whole string.
+    pub fn style_key_at(&self, x: i32, y: i32) -> Option<&str> {
+        let c = self.get(x, y);
+        self.styles
+            .iter()
+            .find(|(key, _)| key.chars().eq(std::iter::once(c)))
+            .map(|(key, _)| key.as_str())
+    }
+
+    /// The raw CSS properties registered for the legend key matching `(x, y)`'s character, if any
+    /// (see [`Grid::style_key_at`]/[`Grid::styles`]), e.g. `"stroke:red"` for a `# r = {stroke:red}`
+    /// legend entry at a cell holding `r`.
+    pub fn style_at(&self, x: i32, y: i32) -> Option<&str> {
+        let c = self.get(x, y);
+        self.styles
+            .iter()
+            .find(|(key, _)| key.chars().eq(std::iter::once(c)))
+            .map(|(_, css)| css.as_str())
+    }
+
+    /// Quoted literal text spans found during construction: (start column, row, literal text)
+    pub fn literals(&self) -> Vec<(i32, i32, String)> {
+        self.literals.clone()
+    }
+
+    /// Get the base scalar of the grapheme cluster at position (x, y), or space if out of bounds.
+    /// Every classification helper (`is_solid_h_line_at`, `is_vertex`, etc.) matches against this
+    /// single `char`; use [`Grid::cluster`] when the whole cluster (e.g. a base letter plus any
+    /// combining accents) is needed, such as when emitting text.
     #[inline]
     pub fn get(&self, x: i32, y: i32) -> char {
         if x < 0 || y < 0 {
@@ -61,10 +242,29 @@ impl Grid {
         if y >= self.height || x >= self.width {
             return ' ';
         }
-        self.chars[y][x]
+        self.chars[y][x].chars().next().unwrap_or(' ')
     }
 
-    /// Mark a cell as used (consumed by path/decoration finding)
+    /// Get the full grapheme cluster at position (x, y) (e.g. a base letter with any combining
+    /// accents attached), or a single space if out of bounds. See [`Grid::get`] for the
+    /// classification-friendly single-scalar accessor.
+    #[inline]
+    pub fn cluster(&self, x: i32, y: i32) -> &str {
+        if x < 0 || y < 0 {
+            return " ";
+        }
+        let x = x as usize;
+        let y = y as usize;
+        if y >= self.height || x >= self.width {
+            return " ";
+        }
+        self.chars[y][x].as_str()
+    }
+
+    /// Mark a cell as used (consumed by path/decoration finding). If `(x, y)` is either half of a
+    /// double-width character, both of its columns are marked together, so a finder that only
+    /// looked at the glyph's left column can't leave its [`WIDE_CONTINUATION`] column dangling as
+    /// unused (and vice versa).
     pub fn set_used(&mut self, x: i32, y: i32) {
         if x < 0 || y < 0 {
             return;
@@ -73,6 +273,11 @@ impl Grid {
         let y = y as usize;
         if y < self.height && x < self.width {
             self.used[y][x] = true;
+            if self.chars[y][x].starts_with(WIDE_CONTINUATION) && x > 0 {
+                self.used[y][x - 1] = true;
+            } else if x + 1 < self.width && self.chars[y][x + 1].starts_with(WIDE_CONTINUATION) {
+                self.used[y][x + 1] = true;
+            }
         }
     }
 
@@ -89,6 +294,35 @@ impl Grid {
         self.used[y][x]
     }
 
+    /// Check if a cell falls inside a quote/backtick-delimited literal-text escape region (see
+    /// [`Grid::literals`]). Path and decoration finders should consult this before classifying a
+    /// cell, so an author's explicit escape always overrides what the character itself looks like.
+    pub fn is_literal(&self, x: i32, y: i32) -> bool {
+        if x < 0 || y < 0 {
+            return false;
+        }
+        let x = x as usize;
+        let y = y as usize;
+        if y >= self.height || x >= self.width {
+            return false;
+        }
+        self.literal[y][x]
+    }
+
+    /// Visual width, in grid columns, of the character at `(x, y)`: `2` if it's the left (real)
+    /// column of a double-width glyph, `0` if it's that glyph's [`WIDE_CONTINUATION`] column, `1`
+    /// otherwise. Lets path finding step past a whole wide glyph in one move instead of tripping
+    /// over its continuation column as if it were its own cell.
+    pub fn display_width(&self, x: i32, y: i32) -> usize {
+        if self.get(x, y) == WIDE_CONTINUATION {
+            0
+        } else if self.get(x + 1, y) == WIDE_CONTINUATION {
+            2
+        } else {
+            1
+        }
+    }
+
     // ========================================================================
     // Line detection at positions
     // ========================================================================
@@ -96,49 +330,49 @@ impl Grid {
     /// Check if there's a solid vertical line at the given position
     pub fn is_solid_v_line_at(&self, x: i32, y: i32) -> bool {
         let c = self.get(x, y);
-        is_solid_v_line(c)
+        self.charset.is_solid_v_line(c)
     }
 
     /// Check if there's a double vertical line at the given position
     pub fn is_double_v_line_at(&self, x: i32, y: i32) -> bool {
         let c = self.get(x, y);
-        is_double_v_line(c)
+        self.charset.is_double_v_line(c)
     }
 
     /// Check if there's a solid horizontal line at the given position
     pub fn is_solid_h_line_at(&self, x: i32, y: i32) -> bool {
         let c = self.get(x, y);
-        is_solid_h_line(c)
+        self.charset.is_solid_h_line(c)
     }
 
     /// Check if there's a squiggle horizontal line at the given position
     pub fn is_squiggle_h_line_at(&self, x: i32, y: i32) -> bool {
         let c = self.get(x, y);
-        is_squiggle_h_line(c)
+        self.charset.is_squiggle_h_line(c)
     }
 
     /// Check if there's a double horizontal line at the given position
     pub fn is_double_h_line_at(&self, x: i32, y: i32) -> bool {
         let c = self.get(x, y);
-        is_double_h_line(c)
+        self.charset.is_double_h_line(c)
     }
 
     /// Check if there's any horizontal line at the given position
     pub fn is_any_h_line_at(&self, x: i32, y: i32) -> bool {
         let c = self.get(x, y);
-        is_any_h_line(c)
+        self.charset.is_any_h_line(c)
     }
 
     /// Check if there's a solid backslash diagonal at the given position
     pub fn is_solid_b_line_at(&self, x: i32, y: i32) -> bool {
         let c = self.get(x, y);
-        is_solid_b_line(c)
+        self.charset.is_solid_b_line(c)
     }
 
     /// Check if there's a solid forward slash diagonal at the given position
     pub fn is_solid_d_line_at(&self, x: i32, y: i32) -> bool {
         let c = self.get(x, y);
-        is_solid_d_line(c)
+        self.charset.is_solid_d_line(c)
     }
 
     // ========================================================================
@@ -163,10 +397,10 @@ impl Grid {
         // Must connect to something above or below
         pred(above)
             || pred(below)
-            || is_top_vertex(above)
-            || is_bottom_vertex(below)
-            || is_arrow_head(above)
-            || is_arrow_head(below)
+            || self.charset.is_top_vertex(above)
+            || self.charset.is_bottom_vertex(below)
+            || self.charset.is_arrow_head(above)
+            || self.charset.is_arrow_head(below)
     }
 
     // ========================================================================
@@ -189,7 +423,7 @@ impl Grid {
         let right = self.get(x + 1, y);
 
         // Must connect to something left or right
-        pred(left) || pred(right) || is_vertex(left) || is_vertex(right)
+        pred(left) || pred(right) || self.charset.is_vertex(left) || self.charset.is_vertex(right)
     }
 
     // ========================================================================
@@ -202,7 +436,7 @@ impl Grid {
         let mut x = start_x;
         while x < self.width as i32 {
             let c = self.get(x, y);
-            if c != ' ' && !self.is_used(x, y) {
+            if c != ' ' && c != WIDE_CONTINUATION && !self.is_used(x, y) {
                 return Some(x);
             }
             x += 1;
@@ -221,7 +455,13 @@ impl Grid {
         while x < self.width as i32 {
             let c = self.get(x, y);
 
-            if c == ' ' {
+            if c == WIDE_CONTINUATION {
+                // Second column of a wide character already pushed above; consume silently
+                // without breaking the run or counting as a space
+                self.set_used(x, y);
+                x += 1;
+                continue;
+            } else if c == ' ' {
                 space_count += 1;
                 if space_count >= spaces && spaces > 0 {
                     // Trim trailing spaces
@@ -239,7 +479,7 @@ impl Grid {
                 break;
             } else {
                 space_count = 0;
-                result.push(c);
+                result.push_str(self.cluster(x, y));
                 self.set_used(x, y);
             }
             x += 1;
@@ -254,14 +494,54 @@ impl Grid {
     }
 }
 
+/// Parse a single legend line of the form `# key = {css-properties}` (e.g.
+/// `# r = {stroke:red}`), returning `(key, css-properties)` if it matches.
+fn parse_legend_line(line: &str) -> Option<(String, String)> {
+    let rest = line.trim().strip_prefix('#')?.trim_start();
+    let (key, rest) = rest.split_once('=')?;
+    let key = key.trim();
+    if key.is_empty() {
+        return None;
+    }
+    let css = rest.trim().strip_prefix('{')?.strip_suffix('}')?.trim();
+    Some((key.to_string(), css.to_string()))
+}
+
+/// Split a trailing legend block (consecutive `# key = {css-properties}` lines, possibly with
+/// blank lines between them) off the end of the diagram, so those lines aren't parsed as diagram
+/// content. Returns the remaining diagram text and the legend entries in the order they appeared.
+fn extract_legend(input: &str) -> (String, Vec<(String, String)>) {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut entries = Vec::new();
+    let mut split_at = lines.len();
+    let mut i = lines.len();
+
+    while i > 0 {
+        let line = lines[i - 1];
+        if line.trim().is_empty() {
+            i -= 1;
+            continue;
+        }
+        match parse_legend_line(line) {
+            Some(entry) => {
+                entries.push(entry);
+                i -= 1;
+                split_at = i;
+            }
+            None => break,
+        }
+    }
+
+    entries.reverse();
+    (lines[..split_at].join("\n"), entries)
+}
+
 /// Preprocess the diagram string:
-/// - Equalize line lengths (pad with spaces)
 /// - Remove common leading whitespace
-/// - Hide marker characters in text (o, v, V)
+/// - Equalize line lengths (pad with spaces)
 fn preprocess(input: &str) -> String {
     let input = remove_leading_space(input);
-    let input = equalize_line_lengths(&input);
-    hide_markers(&input)
+    equalize_line_lengths(&input)
 }
 
 /// Remove common leading whitespace from all lines
@@ -293,15 +573,17 @@ fn remove_leading_space(input: &str) -> String {
         .join("\n")
 }
 
-/// Pad all lines to the same length
+/// Pad all lines to the same visual width (counting double-width characters as two columns,
+/// combining marks as zero), so a line built entirely of narrow characters still lines up with
+/// one mixing in CJK/fullwidth glyphs.
 fn equalize_line_lengths(input: &str) -> String {
     let lines: Vec<&str> = input.lines().collect();
-    let max_len = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let max_len = lines.iter().map(|l| visual_width(l)).max().unwrap_or(0);
 
     lines
         .iter()
         .map(|l| {
-            let len = l.chars().count();
+            let len = visual_width(l);
             if len < max_len {
                 let padding = " ".repeat(max_len - len);
                 format!("{}{}", l, padding)
@@ -313,68 +595,6 @@ fn equalize_line_lengths(input: &str) -> String {
         .join("\n")
 }
 
-/// Hide 'o', 'v', 'V' characters that appear to be part of text
-/// (surrounded by letters, not connected to lines)
-fn hide_markers(input: &str) -> String {
-    let lines: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
-    let height = lines.len();
-
-    let get = |x: i32, y: i32| -> char {
-        if y < 0 || y >= height as i32 {
-            return ' ';
-        }
-        let row = &lines[y as usize];
-        if x < 0 || x >= row.len() as i32 {
-            return ' ';
-        }
-        row[x as usize]
-    };
-
-    let is_letter = |c: char| -> bool { c.is_ascii_alphabetic() };
-
-    let mut result: Vec<Vec<char>> = lines.clone();
-
-    for y in 0..height {
-        for x in 0..lines[y].len() {
-            let c = lines[y][x];
-            let xi = x as i32;
-            let yi = y as i32;
-
-            // Check if o, v, V is part of a word (surrounded by letters)
-            if c == 'o' || c == 'v' || c == 'V' {
-                let left = get(xi - 1, yi);
-                let right = get(xi + 1, yi);
-
-                // If surrounded by letters on left or right, it's part of text
-                if is_letter(left) || is_letter(right) {
-                    // Replace with a placeholder that won't be detected as decoration
-                    // Use a private use Unicode character
-                    result[y][x] = match c {
-                        'o' => '\u{E000}', // Private use for 'o'
-                        'v' => '\u{E001}', // Private use for 'v'
-                        'V' => '\u{E002}', // Private use for 'V'
-                        _ => c,
-                    };
-                }
-            }
-        }
-    }
-
-    result
-        .iter()
-        .map(|row| row.iter().collect::<String>())
-        .collect::<Vec<_>>()
-        .join("\n")
-}
-
-/// Unhide previously hidden marker characters
-pub fn unhide_markers(input: &str) -> String {
-    input
-        .replace('\u{E000}', "o")
-        .replace('\u{E001}', "v")
-        .replace('\u{E002}', "V")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -390,6 +610,37 @@ mod tests {
         assert_eq!(grid.get(1, 1), ' ');
     }
 
+    #[test]
+    fn test_with_charset_overrides_detection() {
+        let default_grid = Grid::new("• •");
+        assert!(!default_grid.charset().is_point('•'));
+
+        let mut custom = CharSet::default();
+        custom.point_chars.push('•');
+        let custom_grid = Grid::with_charset("• •", custom);
+        assert!(custom_grid.charset().is_point('•'));
+    }
+
+    #[test]
+    fn test_trailing_legend_block_is_parsed_and_stripped() {
+        let grid = Grid::new("+--r--+\n\n# r = {stroke:red}\n# b = {stroke:blue}");
+        assert_eq!(
+            grid.styles(),
+            &[("r".to_string(), "stroke:red".to_string()), ("b".to_string(), "stroke:blue".to_string())]
+        );
+        assert_eq!(grid.height, 1);
+        assert_eq!(grid.get(3, 0), 'r');
+        assert_eq!(grid.style_key_at(3, 0), Some("r"));
+        assert_eq!(grid.style_key_at(0, 0), None);
+    }
+
+    #[test]
+    fn test_no_legend_block_leaves_styles_empty() {
+        let grid = Grid::new("+--+\n|  |\n+--+");
+        assert!(grid.styles().is_empty());
+        assert_eq!(grid.style_key_at(0, 0), None);
+    }
+
     #[test]
     fn test_grid_out_of_bounds() {
         let grid = Grid::new("AB\nCD");
@@ -424,9 +675,88 @@ mod tests {
 
     #[test]
     fn test_text_extraction() {
-        // Use text without o, v, V which get hidden as markers
         let mut grid = Grid::new("Test String");
         let text = grid.extract_text(0, 0, 2);
         assert_eq!(text, "Test String");
     }
+
+    #[test]
+    fn test_wide_char_occupies_two_columns() {
+        // "日" is double-width, so the "|" after it lands at grid column 2, not 1, in line with
+        // the "|" below it
+        let grid = Grid::new("日|\n| |\n+-+");
+        assert_eq!(grid.get(0, 0), '日');
+        assert_eq!(grid.get(1, 0), WIDE_CONTINUATION);
+        assert_eq!(grid.get(2, 0), '|');
+        assert_eq!(grid.get(2, 1), '|');
+    }
+
+    #[test]
+    fn test_wide_char_text_extraction_is_not_split_by_continuation() {
+        let mut grid = Grid::new("日本語");
+        let text = grid.extract_text(0, 0, 1);
+        assert_eq!(text, "日本語");
+    }
+
+    #[test]
+    fn test_combining_mark_attaches_to_previous_cell_as_one_cluster() {
+        // "o" + combining diaeresis (U+0308) is one visual cell, not two
+        let grid = Grid::new("o\u{0308}|");
+        assert_eq!(grid.get(0, 0), 'o');
+        assert_eq!(grid.cluster(0, 0), "o\u{0308}");
+        assert_eq!(grid.get(1, 0), '|');
+    }
+
+    #[test]
+    fn test_extract_text_preserves_combining_marks() {
+        let mut grid = Grid::new("o\u{0308}ps");
+        let text = grid.extract_text(0, 0, 1);
+        assert_eq!(text, "o\u{0308}ps");
+    }
+
+    #[test]
+    fn test_display_width() {
+        let grid = Grid::new("日|");
+        assert_eq!(grid.display_width(0, 0), 2);
+        assert_eq!(grid.display_width(1, 0), 0);
+        assert_eq!(grid.display_width(2, 0), 1);
+    }
+
+    #[test]
+    fn test_set_used_marks_both_columns_of_a_wide_char() {
+        let mut grid = Grid::new("日|");
+        grid.set_used(0, 0);
+        assert!(grid.is_used(0, 0));
+        assert!(grid.is_used(1, 0));
+
+        let mut grid = Grid::new("日|");
+        grid.set_used(1, 0);
+        assert!(grid.is_used(0, 0));
+        assert!(grid.is_used(1, 0));
+    }
+
+    #[test]
+    fn test_quoted_literal_text_is_blanked_and_recorded() {
+        let grid = Grid::new(r#""a-b/c""#);
+        assert_eq!(grid.get(0, 0), ' ');
+        assert_eq!(grid.get(1, 0), ' ');
+        assert!(grid.is_used(0, 0));
+        assert!(grid.is_used(6, 0));
+        assert_eq!(grid.literals(), vec![(1, 0, "a-b/c".to_string())]);
+    }
+
+    #[test]
+    fn test_backtick_literal_text_is_also_recognized() {
+        let grid = Grid::new("`o-->v`");
+        assert_eq!(grid.get(0, 0), ' ');
+        assert_eq!(grid.literals(), vec![(1, 0, "o-->v".to_string())]);
+    }
+
+    #[test]
+    fn test_is_literal_marks_the_escaped_span() {
+        let grid = Grid::new(r#""abc" x"#);
+        assert!(grid.is_literal(0, 0));
+        assert!(grid.is_literal(4, 0));
+        assert!(!grid.is_literal(6, 0));
+    }
 }
@@ -36,18 +36,31 @@
 
 mod chars;
 mod decoration;
+mod fill;
 mod finder;
 mod grid;
+mod optimizer;
 mod path;
+mod raster;
+mod shape;
 mod svg;
 
-pub use svg::RenderOptions;
+pub use chars::CharSet;
+pub use decoration::ArrowStyle;
+pub use fill::FillRule;
+#[doc(hidden)]
+pub use raster::RasterError;
+pub use raster::Theme;
+pub use svg::{Palette, RenderOptions};
+
+use std::io;
 
 use decoration::DecorationSet;
 use finder::{find_decorations, find_paths};
 use grid::Grid;
 use path::PathSet;
-use svg::generate_svg;
+use shape::ShapeSet;
+use svg::write_svg;
 
 /// Render an ASCII art diagram to SVG.
 ///
@@ -67,6 +80,36 @@ pub fn render(input: &str) -> String {
     render_with_options(input, &RenderOptions::default())
 }
 
+/// Render an ASCII art diagram directly to a writer, instead of building the whole SVG document
+/// as one `String` first. Useful for large diagrams streamed straight to a file or socket.
+/// [`render`] and [`render_with_options`] are thin wrappers around this that collect the output
+/// into a `String` via a `Vec<u8>` buffer.
+///
+/// # Example
+///
+/// ```rust
+/// use aasvg::{render_to_writer, RenderOptions};
+///
+/// let mut out = Vec::new();
+/// render_to_writer("+--+\n|  |\n+--+", &RenderOptions::default(), &mut out).unwrap();
+/// assert!(String::from_utf8(out).unwrap().contains("<svg"));
+/// ```
+pub fn render_to_writer(input: &str, options: &RenderOptions, w: &mut impl io::Write) -> io::Result<()> {
+    let mut grid = Grid::with_charset(input, options.charset().clone());
+    let mut paths = PathSet::new();
+    let mut shapes = ShapeSet::new();
+    let mut decorations = DecorationSet::new();
+
+    find_paths(&mut grid, &mut paths, options.corner_radius());
+    shape::endorse(&mut paths, &mut shapes);
+    paths.round_corners(options.corner_radius());
+    find_decorations(&mut grid, &paths, &mut decorations);
+    fill::find_fills(&mut grid, &paths, &shapes, &mut decorations, options.fill_rule());
+    optimizer::optimize(&mut paths, &decorations);
+
+    write_svg(w, &mut grid, &paths, &shapes, &decorations, options)
+}
+
 /// Render an ASCII art diagram to SVG with custom options.
 ///
 /// # Example
@@ -82,14 +125,31 @@ pub fn render(input: &str) -> String {
 /// assert!(svg.contains("var(--aasvg-bg)"));
 /// ```
 pub fn render_with_options(input: &str, options: &RenderOptions) -> String {
-    let mut grid = Grid::new(input);
-    let mut paths = PathSet::new();
-    let mut decorations = DecorationSet::new();
-
-    find_paths(&mut grid, &mut paths);
-    find_decorations(&mut grid, &paths, &mut decorations);
+    let mut buf = Vec::new();
+    render_to_writer(input, options, &mut buf).expect("writing SVG to a Vec<u8> is infallible");
+    String::from_utf8(buf).expect("SVG output is always valid UTF-8")
+}
 
-    generate_svg(&mut grid, &paths, &decorations, options)
+/// Render an ASCII art diagram to a PNG, for callers that want a raster image instead of an SVG
+/// string (e.g. a docs pipeline that can't embed SVG).
+///
+/// Since the normal output relies on `prefers-color-scheme`/`var(--aasvg-*)` CSS that can't be
+/// evaluated headlessly, this first bakes in one explicit `theme`'s colors to get a
+/// self-contained SVG, then rasterizes it at `scale`.
+///
+/// Hidden from the public docs for now: it always returns [`RasterError::Unavailable`], since
+/// turning the resolved SVG into pixels needs an embeddable rasterizer (e.g. `tiny-skia`/`resvg`)
+/// behind a `raster` cargo feature, and this source tree has no `Cargo.toml` to declare that
+/// feature or depend on a rasterizer crate. Un-hide once a real rasterizer backs this.
+///
+/// # Errors
+///
+/// Returns [`RasterError::Unavailable`] unconditionally (see above).
+#[doc(hidden)]
+pub fn render_to_png(input: &str, options: &RenderOptions, theme: Theme, scale: f64) -> Result<Vec<u8>, RasterError> {
+    let _themed_svg = raster::resolve_theme(&render_with_options(input, options), theme);
+    let _ = scale;
+    Err(RasterError::Unavailable)
 }
 
 #[cfg(test)]
@@ -101,7 +161,23 @@ mod tests {
         let svg = render("+--+\n|  |\n+--+");
         assert!(svg.starts_with("<svg"));
         assert!(svg.ends_with("</svg>"));
-        assert!(svg.contains("path"));
+        // A fully closed box is endorsed into a single rect rather than four lines
+        assert!(svg.contains("<rect"));
+    }
+
+    #[test]
+    fn test_legend_style_is_applied_to_its_marker_text() {
+        let svg = render("+--r--+\n|     |\n+-----+\n\n# r = {stroke:red}");
+        assert!(svg.contains("style=\"stroke:red\""));
+        assert!(svg.contains("red"));
+    }
+
+    #[test]
+    fn test_double_weight_box_drawing_box() {
+        let svg = render("╔═╗\n║ ║\n╚═╝");
+        // Same as test_simple_box: the double-weight corners connect to the double-weight
+        // sides, so this is endorsed into a single rect rather than four disconnected stubs.
+        assert!(svg.contains("<rect"));
     }
 
     #[test]
@@ -110,6 +186,25 @@ mod tests {
         assert!(svg.contains("polygon"));
     }
 
+    #[test]
+    fn test_jump_bridge_renders_from_real_input() {
+        let svg = render("|\n|\n(\n|\n|");
+        // The jump curve's wider background stroke, stacked under the foreground stroke
+        assert!(svg.contains("stroke-width=\"3\""));
+        assert!(svg.contains(" C "));
+    }
+
+    #[test]
+    fn test_line_end_markers_render_from_real_input() {
+        let svg = render("--◆\n--◉\n--#");
+        // Diamond end: elongated rhombus polygon
+        assert!(svg.contains("<polygon"));
+        // Circle end: a circle offset from the line end
+        assert!(svg.contains("<circle"));
+        // Cross end: a short rotated bar
+        assert!(svg.contains("<line"));
+    }
+
     #[test]
     fn test_css_variables() {
         let svg = render("-");
@@ -145,4 +240,138 @@ mod tests {
         let svg = render_with_options("Hello", &options);
         assert!(!svg.contains("Hello"));
     }
+
+    #[test]
+    fn test_quoted_literal_text_renders_as_text_not_a_line() {
+        let svg = render(r#""a-b/c""#);
+        assert!(svg.contains("a-b/c"));
+        assert!(!svg.contains("<line"));
+        assert!(!svg.contains("<path"));
+    }
+
+    #[test]
+    fn test_default_corner_radius_is_hard_miter() {
+        // Default radius is 0.0, so an explicit curved-vertex character still draws a sharp
+        // corner rather than a bezier arc
+        let svg = render("-.\n |");
+        assert!(!svg.contains(" C "));
+    }
+
+    #[test]
+    fn test_corner_radius_rounds_explicit_vertex() {
+        let options = RenderOptions::new().with_corner_radius(0.4);
+        let svg = render_with_options("-.\n |", &options);
+        assert!(svg.contains(" C "));
+    }
+
+    #[test]
+    fn test_corner_radius_rounds_polyline_elbow_joint() {
+        // An open elbow (not a closed box) merges into a single polyline with a sharp +
+        // joint by default; a nonzero corner_radius should round that joint too
+        let options = RenderOptions::new().with_corner_radius(0.4);
+        let svg = render_with_options("+--\n|\n|", &options);
+        assert!(svg.contains(" C "));
+    }
+
+    #[test]
+    fn test_arrow_style_polygon_is_default() {
+        let svg = render("-->");
+        assert!(svg.contains("<polygon"));
+        assert!(!svg.contains("marker-end"));
+    }
+
+    #[test]
+    fn test_arrow_style_marker_emits_shared_def_and_reference() {
+        let options = RenderOptions::new().with_arrow_style(ArrowStyle::Marker);
+        let svg = render_with_options("-->", &options);
+        assert!(svg.contains("<marker id=\"aasvg-arrow\""));
+        assert!(svg.contains("marker-end=\"url(#aasvg-arrow)\""));
+        assert!(!svg.contains("rotate("));
+    }
+
+    #[test]
+    fn test_arrow_style_marker_def_is_emitted_once_for_many_arrows() {
+        let options = RenderOptions::new().with_arrow_style(ArrowStyle::Marker);
+        let svg = render_with_options("-->\n<--\n-->", &options);
+        assert_eq!(svg.matches("<marker id=\"aasvg-arrow\"").count(), 1);
+        assert!(svg.matches("url(#aasvg-arrow)").count() >= 2);
+    }
+
+    #[test]
+    fn test_css_classes_off_by_default() {
+        let svg = render("-->");
+        assert!(svg.contains("fill=\"var(--aasvg-fill)\""));
+        assert!(!svg.contains("class=\"aasvg-arrow\""));
+    }
+
+    #[test]
+    fn test_css_classes_replace_inline_attrs_on_themed_decorations() {
+        let options = RenderOptions::new().with_css_classes(true);
+        let svg = render_with_options("-->", &options);
+        assert!(svg.contains("class=\"aasvg-arrow\""));
+        assert!(!svg.contains("fill=\"var(--aasvg-fill)\""));
+        assert!(svg.contains(".aasvg-arrow"));
+    }
+
+    #[test]
+    fn test_render_to_writer_matches_render_with_options() {
+        let options = RenderOptions::new().with_backdrop(true);
+        let mut buf = Vec::new();
+        render_to_writer("+--+\n|  |\n+--+", &options, &mut buf).unwrap();
+        let streamed = String::from_utf8(buf).unwrap();
+        assert_eq!(streamed, render_with_options("+--+\n|  |\n+--+", &options));
+    }
+
+    #[test]
+    fn test_custom_charset_recognizes_extra_point_glyph() {
+        let mut charset = CharSet::default();
+        charset.point_chars.push('•');
+        let options = RenderOptions::new().with_charset(charset);
+
+        // By default '•' isn't a recognized point, so it's rendered as plain text
+        assert!(render("--•--").contains('•'));
+        // Once registered as a point glyph it's consumed into a decoration instead
+        assert!(!render_with_options("--•--", &options).contains('•'));
+    }
+
+    #[test]
+    fn test_render_to_png_is_unavailable_without_a_rasterizer() {
+        let result = render_to_png("+--+\n|  |\n+--+", &RenderOptions::default(), Theme::Dark, 1.0);
+        assert_eq!(result, Err(RasterError::Unavailable));
+    }
+
+    #[test]
+    fn test_squiggle_lines_get_a_dasharray_by_default() {
+        let svg = render("~~~");
+        assert!(svg.contains("stroke-dasharray"));
+    }
+
+    #[test]
+    fn test_with_dashed_lines_false_renders_squiggle_as_plain_solid() {
+        let options = RenderOptions::new().with_dashed_lines(false);
+        let svg = render_with_options("~~~", &options);
+        assert!(!svg.contains("stroke-dasharray"));
+        assert!(svg.contains(" Q "));
+    }
+
+    #[test]
+    fn test_with_compact_paths_minifies_d_attributes() {
+        let options = RenderOptions::new().with_compact_paths(true);
+        let svg = render_with_options("+--\n|\n|", &options);
+        assert!(svg.contains("<path"));
+        assert!(!svg.contains("L 8,"));
+    }
+
+    #[test]
+    fn test_custom_palette_is_reflected_in_rendered_svg() {
+        let options = RenderOptions::new().with_theme(Theme::Light).with_palette(Palette::new(
+            "rebeccapurple",
+            "rebeccapurple",
+            "white",
+            "black",
+        ));
+        let svg = render_with_options("+--+\n|  |\n+--+", &options);
+        assert!(svg.contains("--aasvg-stroke: rebeccapurple"));
+        assert!(!svg.contains("@media"));
+    }
 }
@@ -0,0 +1,60 @@
+//! Post-processing pass that shrinks the final `<path>` count by merging straight lines back
+//! together across junctions that [`PathSet::merge_contacts`] has to leave split.
+//!
+//! `merge_contacts` only joins a contact point where exactly two path-ends meet there; a
+//! three-or-more-way junction (a T-intersection, say) blocks it even when two of those ends are
+//! really just the same straight line passing straight through, with a third branching off. This
+//! pass runs after everything else that cares about path boundaries (decoration matching, region
+//! fill) and merges just that residual case, the way svgbob's optimizer collapses collinear runs
+//! to cut down on emitted elements.
+
+use crate::decoration::DecorationSet;
+use crate::path::{PathSet, Vec2, ASPECT, SCALE};
+
+fn close(a: Vec2, b: Vec2) -> bool {
+    let thresh_x = SCALE * 0.75;
+    let thresh_y = SCALE * ASPECT * 0.75;
+    (a.x - b.x).abs() <= thresh_x && (a.y - b.y).abs() <= thresh_y
+}
+
+/// Merge collinear through-junctions in `paths`, never merging through a point that carries one
+/// of `decorations` (so an arrow/point kept at a junction still has a path endpoint to sit on)
+pub fn optimize(paths: &mut PathSet, decorations: &DecorationSet) {
+    paths.merge_collinear_through_junctions(|pos| decorations.iter().any(|d| close(pos, d.pos)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path::Path;
+
+    #[test]
+    fn test_merges_straight_run_through_t_junction() {
+        // Three paths meeting at (2, 0): a straight horizontal run through it, plus a branch
+        // dropping down from the same point. merge_contacts leaves all three split since three
+        // ends touch there; the optimizer should still join the two collinear ones.
+        let mut paths = PathSet::new();
+        paths.insert(Path::line_from_grid(0, 0, 2, 0));
+        paths.insert(Path::line_from_grid(2, 0, 4, 0));
+        paths.insert(Path::line_from_grid(2, 0, 2, 2));
+        let decorations = DecorationSet::new();
+
+        optimize(&mut paths, &decorations);
+
+        assert_eq!(paths.len(), 2);
+    }
+
+    #[test]
+    fn test_does_not_merge_through_decorated_junction() {
+        let mut paths = PathSet::new();
+        paths.insert(Path::line_from_grid(0, 0, 2, 0));
+        paths.insert(Path::line_from_grid(2, 0, 4, 0));
+        paths.insert(Path::line_from_grid(2, 0, 2, 2));
+        let mut decorations = DecorationSet::new();
+        decorations.insert(crate::decoration::Decoration::closed_point(2, 0));
+
+        optimize(&mut paths, &decorations);
+
+        assert_eq!(paths.len(), 3);
+    }
+}
@@ -0,0 +1,1003 @@
+//! Path geometry: grid-to-pixel coordinates and the lines/curves found by the finder.
+
+// Many methods are provided for library consumers but not used internally
+#![allow(dead_code)]
+
+use std::io;
+
+/// Horizontal half-cell size in SVG user units (one grid column is `2 * SCALE` wide)
+pub const SCALE: f64 = 8.0;
+
+/// Vertical stretch factor applied to `SCALE` (grid rows are taller than columns are wide,
+/// matching the look of a monospace terminal font)
+pub const ASPECT: f64 = 1.6;
+
+/// Angle (in degrees) of a diagonal line drawn across one grid cell, accounting for `ASPECT`
+pub fn diagonal_angle() -> f64 {
+    (ASPECT).atan().to_degrees()
+}
+
+/// A point in SVG user-space coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Vec2 {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Vec2 {
+    /// Create a point directly from SVG coordinates
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    /// Map a grid cell to the SVG coordinates of its center
+    pub fn from_grid(x: i32, y: i32) -> Self {
+        Self {
+            x: x as f64 * SCALE * 2.0 + SCALE,
+            y: y as f64 * SCALE * ASPECT * 2.0 + SCALE * ASPECT,
+        }
+    }
+
+    /// Offset this point by a fraction of a cell (e.g. `-0.5` is half a cell to the left/above)
+    pub fn offset(self, dx: f64, dy: f64) -> Self {
+        Self {
+            x: self.x + dx * SCALE * 2.0,
+            y: self.y + dy * SCALE * ASPECT * 2.0,
+        }
+    }
+
+    /// Map a grid cell to the SVG coordinates of one of its named sub-cell anchor points, instead
+    /// of always landing on the cell center like [`Vec2::from_grid`]. Lets decorations and curve
+    /// control points (e.g. [`crate::decoration::Decoration::jump`]'s bridge) land more precisely
+    /// than a whole-cell offset allows.
+    pub fn from_block(x: i32, y: i32, block: Block) -> Self {
+        let (dx, dy) = block.offset();
+        Self::from_grid(x, y).offset(dx, dy)
+    }
+}
+
+/// A named anchor point on the 5×5 sub-cell lattice (corners, edge midpoints, quarter points along
+/// each edge, and the center) used by [`Vec2::from_block`] to position things more precisely within
+/// a single grid cell than [`Vec2::from_grid`]'s cell-center default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Block {
+    /// Cell center, same as `Vec2::from_grid`
+    Center,
+    Top,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    /// Quarter-cell point on the top edge, left of center
+    TopQuarterLeft,
+    /// Quarter-cell point on the top edge, right of center
+    TopQuarterRight,
+    /// Quarter-cell point on the bottom edge, left of center
+    BottomQuarterLeft,
+    /// Quarter-cell point on the bottom edge, right of center
+    BottomQuarterRight,
+    /// Quarter-cell point on the left edge, above center
+    LeftQuarterTop,
+    /// Quarter-cell point on the left edge, below center
+    LeftQuarterBottom,
+    /// Quarter-cell point on the right edge, above center
+    RightQuarterTop,
+    /// Quarter-cell point on the right edge, below center
+    RightQuarterBottom,
+}
+
+impl Block {
+    /// This anchor's `(dx, dy)` offset from the cell center, in fractions of a cell, as expected
+    /// by [`Vec2::offset`]
+    fn offset(self) -> (f64, f64) {
+        match self {
+            Block::Center => (0.0, 0.0),
+            Block::Top => (0.0, -0.5),
+            Block::Bottom => (0.0, 0.5),
+            Block::Left => (-0.5, 0.0),
+            Block::Right => (0.5, 0.0),
+            Block::TopLeft => (-0.5, -0.5),
+            Block::TopRight => (0.5, -0.5),
+            Block::BottomLeft => (-0.5, 0.5),
+            Block::BottomRight => (0.5, 0.5),
+            Block::TopQuarterLeft => (-0.25, -0.5),
+            Block::TopQuarterRight => (0.25, -0.5),
+            Block::BottomQuarterLeft => (-0.25, 0.5),
+            Block::BottomQuarterRight => (0.25, 0.5),
+            Block::LeftQuarterTop => (-0.5, -0.25),
+            Block::LeftQuarterBottom => (-0.5, 0.25),
+            Block::RightQuarterTop => (0.5, -0.25),
+            Block::RightQuarterBottom => (0.5, 0.25),
+        }
+    }
+}
+
+/// Whether `a`, `b`, `c` fall on a single straight line (within floating-point slop)
+fn collinear(a: Vec2, b: Vec2, c: Vec2) -> bool {
+    let cross = (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x);
+    cross.abs() < 1e-6
+}
+
+/// Whether a path ending at `near` (having come from `far`) and another path also touching
+/// `near` (going on to `other_far`) together trace one uninterrupted straight line through
+/// `near`, rather than turning back on themselves or meeting at an angle
+fn straight_through(far: Vec2, near: Vec2, other_far: Vec2) -> bool {
+    if !collinear(far, near, other_far) {
+        return false;
+    }
+    let u = (near.x - far.x, near.y - far.y);
+    let v = (other_far.x - near.x, other_far.y - near.y);
+    u.0 * v.0 + u.1 * v.1 > 0.0
+}
+
+/// The point `dist` units from `from` along the straight line towards `to`, clamped so it never
+/// overshoots `to` itself
+fn point_toward(from: Vec2, to: Vec2, dist: f64) -> Vec2 {
+    let dx = to.x - from.x;
+    let dy = to.y - from.y;
+    let len = (dx * dx + dy * dy).sqrt();
+    if len < 1e-9 {
+        return from;
+    }
+    let t = (dist / len).min(1.0);
+    Vec2::new(from.x + dx * t, from.y + dy * t)
+}
+
+/// The geometric kind of a [`Path`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathKind {
+    /// Straight line segment
+    Line,
+    /// Cubic bezier curve
+    Curve,
+    /// Multiple lines/curves chained end-to-end, built by [`PathSet::merge_contacts`]
+    Polyline,
+}
+
+/// One drawing command within a [`Path`], carried out from wherever the previous segment
+/// (or `Path::start`, for the first one) left off
+#[derive(Debug, Clone, Copy)]
+pub struct Segment {
+    pub to: Vec2,
+    /// Control points, present only when this segment is a bezier curve
+    pub ctrl1: Option<Vec2>,
+    pub ctrl2: Option<Vec2>,
+}
+
+impl Segment {
+    fn line(to: Vec2) -> Self {
+        Self { to, ctrl1: None, ctrl2: None }
+    }
+
+    fn curve(ctrl1: Vec2, ctrl2: Vec2, to: Vec2) -> Self {
+        Self { to, ctrl1: Some(ctrl1), ctrl2: Some(ctrl2) }
+    }
+}
+
+/// One token in a `d` attribute's numeric token stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DToken {
+    /// A command letter (`M`, `L`, `C`, `Q`, ...)
+    Command,
+    /// A (possibly signed, possibly fractional) number
+    Number,
+}
+
+/// Split a `d` attribute's value into command letters and numbers, discarding the whitespace/comma
+/// separators `build_d` inserts between them; [`compact_path_data`] decides which of those
+/// separators are actually load-bearing and reinserts only those.
+fn tokenize_d(d: &str) -> Vec<(DToken, &str)> {
+    let bytes = d.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i] as char;
+        if c.is_ascii_whitespace() || c == ',' {
+            i += 1;
+        } else if c.is_ascii_alphabetic() {
+            tokens.push((DToken::Command, &d[i..i + 1]));
+            i += 1;
+        } else {
+            let start = i;
+            if c == '-' {
+                i += 1;
+            }
+            while i < bytes.len() && (bytes[i] as char).is_ascii_digit() {
+                i += 1;
+            }
+            if i < bytes.len() && bytes[i] as char == '.' {
+                i += 1;
+                while i < bytes.len() && (bytes[i] as char).is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            tokens.push((DToken::Number, &d[start..i]));
+        }
+    }
+
+    tokens
+}
+
+/// Drop a fractional number's leading zero (`0.5` -> `.5`, `-0.1` -> `-.1`) and any trailing zeros
+/// in its fraction (`10.50` -> `10.5`, `10.0` -> `10`); integers are returned unchanged.
+fn compact_number(n: &str) -> String {
+    let Some(dot) = n.find('.') else {
+        return n.to_string();
+    };
+
+    let mut n = n.to_string();
+    while n.ends_with('0') {
+        n.pop();
+    }
+    if n.ends_with('.') {
+        n.pop();
+    }
+
+    if n.len() > dot {
+        if dot == 1 && n.starts_with('0') {
+            n.remove(0);
+        } else if dot == 2 && n.starts_with("-0") {
+            n.remove(1);
+        }
+    }
+
+    n
+}
+
+/// Minify a `d` attribute's coordinate output the way svgtypes' `WriteOptions` does: shrink each
+/// number with [`compact_number`], then drop the separator between two adjacent numbers wherever
+/// the second one's sign or decimal point already delimits it unambiguously (`M 10,-20` -> `M10-20`),
+/// keeping a comma only where the second number would otherwise fuse with the one before it. A number
+/// is never separated from a preceding command letter, since a letter can't be mistaken for a digit.
+///
+/// In elliptical-arc (`A`) argument runs, each large-arc/sweep flag is also joined directly to the
+/// number that follows it (`1,10` -> `110`), since a flag is always a single digit — though nothing
+/// in this crate currently emits an `A` command, since every path here is built from `M`/`L`/`C`/`Q`
+/// segments.
+fn compact_path_data(d: &str) -> String {
+    let mut out = String::with_capacity(d.len());
+    let mut prev: Option<(DToken, String)> = None;
+    // Index of the next number within the current `A` command's 7-number argument group
+    // (rx ry x-axis-rotation large-arc-flag sweep-flag x y), or `None` outside an `A` command
+    let mut arc_arg: Option<usize> = None;
+    let mut prev_is_arc_flag = false;
+
+    for (kind, raw) in tokenize_d(d) {
+        let text = match kind {
+            DToken::Number => compact_number(raw),
+            DToken::Command => raw.to_string(),
+        };
+
+        if let Some((DToken::Number, prev_text)) = &prev {
+            if kind == DToken::Number {
+                let needs_separator = if prev_is_arc_flag || text.starts_with('-') {
+                    false
+                } else if text.starts_with('.') {
+                    !prev_text.contains('.')
+                } else {
+                    true
+                };
+                if needs_separator {
+                    out.push(',');
+                }
+            }
+        }
+
+        out.push_str(&text);
+        prev_is_arc_flag = kind == DToken::Number && matches!(arc_arg, Some(3 | 4));
+        arc_arg = match kind {
+            DToken::Command => raw.eq_ignore_ascii_case("a").then_some(0),
+            DToken::Number => arc_arg.map(|i| (i + 1) % 7),
+        };
+        prev = Some((kind, text));
+    }
+
+    out
+}
+
+/// A line, curve, or chain of them found in the grid
+#[derive(Debug, Clone)]
+pub struct Path {
+    pub kind: PathKind,
+    pub start: Vec2,
+    /// One entry for `Line`/`Curve`, two or more for a merged `Polyline`
+    pub segments: Vec<Segment>,
+    /// Grid-space endpoints, present only for axis-aligned lines built via `line_from_grid`
+    pub grid_start: Option<(i32, i32)>,
+    pub grid_end: Option<(i32, i32)>,
+    pub double: bool,
+    pub squiggle: bool,
+}
+
+impl Path {
+    /// Create a straight line between two SVG-space points
+    pub fn line(start: Vec2, end: Vec2) -> Self {
+        Self {
+            kind: PathKind::Line,
+            start,
+            segments: vec![Segment::line(end)],
+            grid_start: None,
+            grid_end: None,
+            double: false,
+            squiggle: false,
+        }
+    }
+
+    /// Create a straight line between two grid cells, keeping the grid coordinates around so
+    /// later lookups (e.g. arrow head attachment) can find it by cell position
+    pub fn line_from_grid(x1: i32, y1: i32, x2: i32, y2: i32) -> Self {
+        let mut path = Self::line(Vec2::from_grid(x1, y1), Vec2::from_grid(x2, y2));
+        path.grid_start = Some((x1, y1));
+        path.grid_end = Some((x2, y2));
+        path
+    }
+
+    /// Create a straight line between two grid cells that each hold one end of a `/`/`\` run,
+    /// keeping the grid coordinates around like [`Path::line_from_grid`].
+    ///
+    /// Unlike a horizontal/vertical line, a `/`/`\` glyph is drawn corner-to-corner within its
+    /// own cell rather than reaching only to the cell center, so `(x1, y1)`/`(x2, y2)` are offset
+    /// half a cell out to the actual corner the glyph touches. `down_right` selects which pair of
+    /// corners: `true` for a `\`-style run (top-left of the first cell to bottom-right of the
+    /// last), `false` for a `/`-style run (bottom-left to top-right). This is what lets one run's
+    /// endpoint land exactly on an adjacent run's matching corner during
+    /// [`PathSet::merge_contacts`], instead of a full cell short of it.
+    pub fn diagonal_from_grid(x1: i32, y1: i32, x2: i32, y2: i32, down_right: bool) -> Self {
+        let (dx1, dy1, dx2, dy2) = if down_right {
+            (-0.5, -0.5, 0.5, 0.5)
+        } else {
+            (-0.5, 0.5, 0.5, -0.5)
+        };
+        let start = Vec2::from_grid(x1, y1).offset(dx1, dy1);
+        let end = Vec2::from_grid(x2, y2).offset(dx2, dy2);
+        let mut path = Self::line(start, end);
+        path.grid_start = Some((x1, y1));
+        path.grid_end = Some((x2, y2));
+        path
+    }
+
+    /// Create a cubic bezier curve between two SVG-space points
+    pub fn curve(start: Vec2, end: Vec2, ctrl1: Vec2, ctrl2: Vec2) -> Self {
+        Self {
+            kind: PathKind::Curve,
+            start,
+            segments: vec![Segment::curve(ctrl1, ctrl2, end)],
+            grid_start: None,
+            grid_end: None,
+            double: false,
+            squiggle: false,
+        }
+    }
+
+    pub fn with_double(mut self, double: bool) -> Self {
+        self.double = double;
+        self
+    }
+
+    pub fn with_squiggle(mut self, squiggle: bool) -> Self {
+        self.squiggle = squiggle;
+        self
+    }
+
+    /// The path's last point
+    pub fn end(&self) -> Vec2 {
+        self.segments.last().map(|s| s.to).unwrap_or(self.start)
+    }
+
+    /// The same path traced in the opposite direction
+    fn reversed(&self) -> Path {
+        let mut points = vec![self.start];
+        points.extend(self.segments.iter().map(|s| s.to));
+        let mut ctrls: Vec<(Option<Vec2>, Option<Vec2>)> =
+            self.segments.iter().map(|s| (s.ctrl1, s.ctrl2)).collect();
+        points.reverse();
+        ctrls.reverse();
+
+        let segments = points[1..]
+            .iter()
+            .zip(ctrls.iter())
+            // Reversing a curve also swaps its control points so the bezier still bows the
+            // same way when walked back-to-front
+            .map(|(&to, &(c1, c2))| Segment { to, ctrl1: c2, ctrl2: c1 })
+            .collect();
+
+        Path {
+            kind: self.kind,
+            start: points[0],
+            segments,
+            grid_start: self.grid_end,
+            grid_end: self.grid_start,
+            double: self.double,
+            squiggle: self.squiggle,
+        }
+    }
+
+    /// Round this polyline's interior straight-to-straight joints by `radius` grid-cell units,
+    /// the same way [`crate::finder`] rounds an explicit `.`/`'` vertex: each such joint is
+    /// replaced with a bezier arc that starts/ends `radius` short of the corner along its
+    /// adjacent segments, with both control points at the corner. Joints that are already a
+    /// curve (an explicit vertex character), a straight run through a collinear point, or
+    /// `radius <= 0.0` are left untouched.
+    fn with_rounded_corners(&self, radius: f64) -> Path {
+        if self.kind != PathKind::Polyline || radius <= 0.0 || self.segments.len() < 2 {
+            return self.clone();
+        }
+
+        let trim = radius * SCALE * 2.0;
+        let mut points = vec![self.start];
+        points.extend(self.segments.iter().map(|s| s.to));
+        let is_line: Vec<bool> = self.segments.iter().map(|s| s.ctrl1.is_none()).collect();
+
+        let n = self.segments.len();
+        let mut round_at = vec![false; n + 1];
+        for k in 1..n {
+            if is_line[k - 1] && is_line[k] && !collinear(points[k - 1], points[k], points[k + 1]) {
+                round_at[k] = true;
+            }
+        }
+
+        let mut segments = Vec::new();
+        for i in 0..n {
+            if !is_line[i] {
+                segments.push(self.segments[i]);
+                continue;
+            }
+
+            let end = if round_at[i + 1] {
+                point_toward(points[i + 1], points[i], trim)
+            } else {
+                points[i + 1]
+            };
+            segments.push(Segment::line(end));
+
+            if round_at[i + 1] {
+                let next_start = point_toward(points[i + 1], points[i + 2], trim);
+                segments.push(Segment::curve(points[i + 1], points[i + 1], next_start));
+            }
+        }
+
+        Path { segments, ..self.clone() }
+    }
+
+    /// Chain this path to `other`, joining them at the anchors identified by `self_is_start`
+    /// and `other_is_start` (whichever endpoint of each path touches the shared contact point)
+    pub(crate) fn joined_to(&self, self_is_start: bool, other: &Path, other_is_start: bool) -> Path {
+        // Re-orient both paths so `self` ends where `other` begins
+        let head = if self_is_start { self.reversed() } else { self.clone() };
+        let tail = if other_is_start { other.clone() } else { other.reversed() };
+
+        let mut segments = head.segments;
+        segments.extend(tail.segments);
+
+        Path {
+            kind: PathKind::Polyline,
+            start: head.start,
+            segments,
+            grid_start: head.grid_start,
+            grid_end: tail.grid_end,
+            double: self.double || other.double,
+            squiggle: self.squiggle || other.squiggle,
+        }
+    }
+
+    /// Whether this path's grid endpoints form a horizontal line
+    fn is_grid_horizontal(&self) -> bool {
+        matches!((self.grid_start, self.grid_end), (Some((_, y1)), Some((_, y2))) if y1 == y2)
+    }
+
+    /// Whether this path's grid endpoints form a vertical line
+    fn is_grid_vertical(&self) -> bool {
+        matches!((self.grid_start, self.grid_end), (Some((x1, _)), Some((x2, _))) if x1 == x2)
+    }
+
+    /// Build the `d` attribute value tracing this path's segments, minified per
+    /// [`RenderOptions::with_compact_paths`](crate::RenderOptions::with_compact_paths) when `compact`
+    /// is set
+    fn build_d(&self, compact: bool) -> String {
+        let mut d = format!("M {},{}", self.start.x, self.start.y);
+        let mut from = self.start;
+
+        for seg in &self.segments {
+            if self.squiggle && self.kind != PathKind::Polyline {
+                // Approximate a wave with a single quadratic bump through the midpoint
+                let mid_x = (from.x + seg.to.x) / 2.0;
+                let mid_y = (from.y + seg.to.y) / 2.0 - SCALE * ASPECT * 0.5;
+                d.push_str(&format!(" Q {},{} {},{}", mid_x, mid_y, seg.to.x, seg.to.y));
+            } else if let (Some(c1), Some(c2)) = (seg.ctrl1, seg.ctrl2) {
+                d.push_str(&format!(" C {},{} {},{} {},{}", c1.x, c1.y, c2.x, c2.y, seg.to.x, seg.to.y));
+            } else {
+                d.push_str(&format!(" L {},{}", seg.to.x, seg.to.y));
+            }
+            from = seg.to;
+        }
+
+        if compact {
+            compact_path_data(&d)
+        } else {
+            d
+        }
+    }
+
+    /// Generate the SVG `<path>` element for this segment (or chain of segments)
+    pub fn to_svg(&self) -> String {
+        self.to_svg_with_markers(false, false, false, true)
+    }
+
+    /// Generate the SVG `<path>` element, attaching `marker-start`/`marker-end` references to the
+    /// shared `#aasvg-arrow` marker (see [`crate::decoration::ArrowStyle::Marker`]) instead of
+    /// relying on a separately-drawn arrowhead decoration
+    pub fn to_svg_with_markers(&self, marker_start: bool, marker_end: bool, compact: bool, dashed_lines: bool) -> String {
+        let mut buf = Vec::new();
+        self.write_svg_with_markers(&mut buf, marker_start, marker_end, compact, dashed_lines)
+            .expect("writing SVG to a Vec<u8> is infallible");
+        String::from_utf8(buf).expect("SVG output is always valid UTF-8")
+    }
+
+    /// Write the SVG `<path>` element for this segment (or chain of segments) directly to `w`
+    /// instead of building an intermediate `String` first; [`Path::to_svg`] is a thin wrapper
+    /// around this. `compact` minifies the `d` attribute per
+    /// [`RenderOptions::with_compact_paths`](crate::RenderOptions::with_compact_paths)
+    pub fn write_svg(&self, w: &mut impl io::Write, compact: bool) -> io::Result<()> {
+        self.write_svg_with_markers(w, false, false, compact, true)
+    }
+
+    /// Write the SVG `<path>` element directly to `w`; see [`Path::to_svg_with_markers`] for what
+    /// `marker_start`/`marker_end` add, and [`RenderOptions::with_compact_paths`](crate::RenderOptions::with_compact_paths)
+    /// for what `compact` does to the `d` attribute. `dashed_lines` controls whether a squiggle
+    /// (`~`) path also gets a `stroke-dasharray`, per
+    /// [`RenderOptions::with_dashed_lines`](crate::RenderOptions::with_dashed_lines).
+    pub fn write_svg_with_markers(
+        &self,
+        w: &mut impl io::Write,
+        marker_start: bool,
+        marker_end: bool,
+        compact: bool,
+        dashed_lines: bool,
+    ) -> io::Result<()> {
+        let d = self.build_d(compact);
+        let stroke_width = if self.double { "3" } else { "2" };
+
+        let mut markers = String::new();
+        if marker_start {
+            markers.push_str(" marker-start=\"url(#aasvg-arrow)\"");
+        }
+        if marker_end {
+            markers.push_str(" marker-end=\"url(#aasvg-arrow)\"");
+        }
+
+        let dash = if self.squiggle && dashed_lines {
+            " stroke-dasharray=\"4,2\""
+        } else {
+            ""
+        };
+
+        writeln!(
+            w,
+            "<path d=\"{}\" fill=\"none\" stroke=\"var(--aasvg-stroke)\" stroke-width=\"{}\"{}{}/>",
+            d, stroke_width, dash, markers
+        )
+    }
+}
+
+/// Collection of all paths found in a diagram
+#[derive(Debug, Default)]
+pub struct PathSet {
+    paths: Vec<Path>,
+}
+
+impl PathSet {
+    pub fn new() -> Self {
+        Self { paths: Vec::new() }
+    }
+
+    pub fn insert(&mut self, path: Path) {
+        self.paths.push(path);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Path> {
+        self.paths.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// A horizontal path's right end lands just left of (x, y), as when a `>` arrow follows `---`
+    pub fn left_ends_at(&self, x: i32, y: i32) -> bool {
+        self.paths.iter().any(|p| {
+            p.is_grid_horizontal()
+                && matches!((p.grid_start, p.grid_end), (Some((x1, gy)), Some((x2, _))) if gy == y && x1.max(x2) == x - 1)
+        })
+    }
+
+    /// A horizontal path's left end lands just right of (x, y), as when a `<` arrow precedes `---`
+    pub fn right_ends_at(&self, x: i32, y: i32) -> bool {
+        self.paths.iter().any(|p| {
+            p.is_grid_horizontal()
+                && matches!((p.grid_start, p.grid_end), (Some((x1, gy)), Some((x2, _))) if gy == y && x1.min(x2) == x + 1)
+        })
+    }
+
+    /// A vertical path's bottom end lands just above (x, y), as when a `v`/`V` arrow follows `|`
+    pub fn up_ends_at(&self, x: i32, y: i32) -> bool {
+        self.paths.iter().any(|p| {
+            p.is_grid_vertical()
+                && matches!((p.grid_start, p.grid_end), (Some((gx, y1)), Some((_, y2))) if gx == x && y1.max(y2) == y - 1)
+        })
+    }
+
+    /// A vertical path's top end lands just below (x, y), as when a `^` arrow precedes `|`
+    pub fn down_ends_at(&self, x: i32, y: i32) -> bool {
+        self.paths.iter().any(|p| {
+            p.is_grid_vertical()
+                && matches!((p.grid_start, p.grid_end), (Some((gx, y1)), Some((_, y2))) if gx == x && y1.min(y2) == y + 1)
+        })
+    }
+
+    /// A horizontal path covers (x, y), anywhere from one endpoint to the other (inclusive)
+    pub fn horizontal_passes_through(&self, x: i32, y: i32) -> bool {
+        self.paths.iter().any(|p| {
+            p.is_grid_horizontal()
+                && matches!((p.grid_start, p.grid_end), (Some((x1, gy)), Some((x2, _))) if gy == y && x1.min(x2) <= x && x <= x1.max(x2))
+        })
+    }
+
+    /// A vertical path covers (x, y), anywhere from one endpoint to the other (inclusive)
+    pub fn vertical_passes_through(&self, x: i32, y: i32) -> bool {
+        self.paths.iter().any(|p| {
+            p.is_grid_vertical()
+                && matches!((p.grid_start, p.grid_end), (Some((gx, y1)), Some((_, y2))) if gx == x && y1.min(y2) <= y && y <= y1.max(y2))
+        })
+    }
+
+    /// A forward-slash (`/`) diagonal's top-right end lands just down-left of (x, y)
+    pub fn diagonal_up_ends_at(&self, x: i32, y: i32) -> bool {
+        self.paths.iter().any(|p| {
+            matches!((p.grid_start, p.grid_end), (Some((x1, y1)), Some((x2, y2)))
+                if x1 != x2 && y1 != y2 && (x1 - x2).abs() == (y1 - y2).abs()
+                && x1.max(x2) == x - 1 && y1.min(y2) == y + 1)
+        })
+    }
+
+    /// A backslash (`\`) diagonal's bottom-right end lands just up-left of (x, y)
+    pub fn back_diagonal_down_ends_at(&self, x: i32, y: i32) -> bool {
+        self.paths.iter().any(|p| {
+            matches!((p.grid_start, p.grid_end), (Some((x1, y1)), Some((x2, y2)))
+                if x1 != x2 && y1 != y2 && (x1 - x2).abs() == (y1 - y2).abs()
+                && x1.max(x2) == x - 1 && y1.max(y2) == y - 1)
+        })
+    }
+
+    /// A forward-slash (`/`) diagonal's bottom-left end lands just up-right of (x, y)
+    pub fn diagonal_down_ends_at(&self, x: i32, y: i32) -> bool {
+        self.paths.iter().any(|p| {
+            matches!((p.grid_start, p.grid_end), (Some((x1, y1)), Some((x2, y2)))
+                if x1 != x2 && y1 != y2 && (x1 - x2).abs() == (y1 - y2).abs()
+                && x1.min(x2) == x + 1 && y1.max(y2) == y - 1)
+        })
+    }
+
+    /// A backslash (`\`) diagonal's top-left end lands just down-right of (x, y)
+    pub fn back_diagonal_up_ends_at(&self, x: i32, y: i32) -> bool {
+        self.paths.iter().any(|p| {
+            matches!((p.grid_start, p.grid_end), (Some((x1, y1)), Some((x2, y2)))
+                if x1 != x2 && y1 != y2 && (x1 - x2).abs() == (y1 - y2).abs()
+                && x1.min(x2) == x + 1 && y1.min(y2) == y + 1)
+        })
+    }
+
+    /// Union paths that touch end-to-end into single multi-segment polylines.
+    ///
+    /// Two paths are "in contact" when an endpoint of one lies within half a cell of an
+    /// endpoint of the other — the full half-cell, not a fraction of it, since a straight line's
+    /// endpoint sits at its vertex's cell center while a `/`/`\` diagonal's endpoint is offset to
+    /// the corner of its own cell (see [`Path::diagonal_from_grid`]), which puts the two up to a
+    /// full half-cell apart even when they represent the same drawn point. A chain keeps growing
+    /// through a contact point as long as it is a simple pass-through (exactly two path-ends meet
+    /// there); a point where three or more path-ends meet is a true junction (e.g. a
+    /// T-intersection) and stays split, so the chains built here never cross one.
+    pub fn merge_contacts(&mut self) {
+        let thresh_x = SCALE + 0.01;
+        let thresh_y = SCALE * ASPECT + 0.01;
+        let close = |a: Vec2, b: Vec2| (a.x - b.x).abs() <= thresh_x && (a.y - b.y).abs() <= thresh_y;
+
+        loop {
+            let n = self.paths.len();
+            let anchor = |i: usize, is_start: bool| {
+                if is_start {
+                    self.paths[i].start
+                } else {
+                    self.paths[i].end()
+                }
+            };
+
+            let mut join = None;
+            'search: for i in 0..n {
+                for i_is_start in [true, false] {
+                    let pi = anchor(i, i_is_start);
+                    let mut contacts = Vec::new();
+                    for j in 0..n {
+                        if j == i {
+                            continue;
+                        }
+                        for j_is_start in [true, false] {
+                            if close(pi, anchor(j, j_is_start)) {
+                                contacts.push((j, j_is_start));
+                            }
+                        }
+                    }
+                    if contacts.len() == 1 {
+                        join = Some((i, i_is_start, contacts[0].0, contacts[0].1));
+                        break 'search;
+                    }
+                }
+            }
+
+            let Some((i, i_is_start, j, j_is_start)) = join else {
+                break;
+            };
+
+            let merged = self.paths[i].joined_to(i_is_start, &self.paths[j], j_is_start);
+            let keep = i.min(j);
+            let drop = i.max(j);
+            self.paths[keep] = merged;
+            self.paths.remove(drop);
+        }
+    }
+
+    /// Merge the two straight paths at a junction point that continue each other in a straight
+    /// line, leaving any other paths touching that point (e.g. a T-junction's branch) alone.
+    ///
+    /// [`PathSet::merge_contacts`] only merges a contact point where exactly two path-ends touch;
+    /// a three-or-more-way junction is left fully split, even when two of those ends are really
+    /// just the same straight line passing through with a third branching off it. This catches
+    /// that residual case: a junction point is skipped entirely when `is_decorated` says a
+    /// decoration sits there (so an arrow/point kept at the junction still has a path endpoint to
+    /// attach to), and only an exactly-[`PathKind::Line`] pair running straight through — not
+    /// merely touching at an angle — is merged, so branches and real corners are left untouched.
+    pub(crate) fn merge_collinear_through_junctions(&mut self, is_decorated: impl Fn(Vec2) -> bool) {
+        let thresh_x = SCALE * 0.75;
+        let thresh_y = SCALE * ASPECT * 0.75;
+        let close = |a: Vec2, b: Vec2| (a.x - b.x).abs() <= thresh_x && (a.y - b.y).abs() <= thresh_y;
+
+        loop {
+            let n = self.paths.len();
+            let anchor = |i: usize, is_start: bool| {
+                if is_start {
+                    self.paths[i].start
+                } else {
+                    self.paths[i].end()
+                }
+            };
+
+            let mut merge = None;
+            'search: for i in 0..n {
+                if self.paths[i].kind != PathKind::Line {
+                    continue;
+                }
+                for i_is_start in [true, false] {
+                    let near = anchor(i, i_is_start);
+                    if is_decorated(near) {
+                        continue;
+                    }
+                    let far = anchor(i, !i_is_start);
+
+                    for j in (i + 1)..n {
+                        if self.paths[j].kind != PathKind::Line {
+                            continue;
+                        }
+                        for j_is_start in [true, false] {
+                            if !close(near, anchor(j, j_is_start)) {
+                                continue;
+                            }
+                            let other_far = anchor(j, !j_is_start);
+                            if straight_through(far, near, other_far) {
+                                merge = Some((i, i_is_start, j, j_is_start));
+                                break 'search;
+                            }
+                        }
+                    }
+                }
+            }
+
+            let Some((i, i_is_start, j, j_is_start)) = merge else {
+                break;
+            };
+
+            let merged = self.paths[i].joined_to(i_is_start, &self.paths[j], j_is_start);
+            self.paths[i] = merged;
+            self.paths.remove(j);
+        }
+    }
+
+    /// Round every merged polyline's interior `+`/L-shaped joints by `radius` grid-cell units
+    /// (see [`Path::with_rounded_corners`]). Called after [`PathSet::merge_contacts`] and shape
+    /// endorsement, so a fully closed box outline — already turned into a `Shape::rect` by then —
+    /// never passes through here; only open polylines (elbow connectors, T-junctions, etc.) do.
+    pub fn round_corners(&mut self, radius: f64) {
+        for path in &mut self.paths {
+            *path = path.with_rounded_corners(radius);
+        }
+    }
+
+    /// Remove the paths at the given indices (must be sorted ascending), as used by the
+    /// shape endorsement pass once a loop of paths has been folded into a `Shape`
+    pub fn retain_indices(&mut self, remove: &[usize]) {
+        let mut remove = remove.iter();
+        let mut next = remove.next().copied();
+        let mut i = 0;
+        self.paths.retain(|_| {
+            let keep = next != Some(i);
+            if !keep {
+                next = remove.next().copied();
+            }
+            i += 1;
+            keep
+        });
+    }
+
+    /// Generate SVG for all paths
+    pub fn to_svg(&self) -> String {
+        let mut buf = Vec::new();
+        self.write_svg(&mut buf, false, true)
+            .expect("writing SVG to a Vec<u8> is infallible");
+        String::from_utf8(buf).expect("SVG output is always valid UTF-8")
+    }
+
+    /// Generate SVG for all paths, attaching `marker-start`/`marker-end` to the path at index `i`
+    /// when `ends[i]` says so (see [`crate::decoration::ArrowStyle::Marker`]). `ends` is indexed
+    /// in the same order as [`PathSet::iter`].
+    pub fn to_svg_with_markers(&self, ends: &[(bool, bool)], compact: bool, dashed_lines: bool) -> String {
+        let mut buf = Vec::new();
+        self.write_svg_with_markers(&mut buf, ends, compact, dashed_lines)
+            .expect("writing SVG to a Vec<u8> is infallible");
+        String::from_utf8(buf).expect("SVG output is always valid UTF-8")
+    }
+
+    /// Write SVG for all paths directly to `w`; [`PathSet::to_svg`] is a thin wrapper around this.
+    /// `compact` minifies each path's `d` attribute per
+    /// [`RenderOptions::with_compact_paths`](crate::RenderOptions::with_compact_paths)
+    pub fn write_svg(&self, w: &mut impl io::Write, compact: bool, dashed_lines: bool) -> io::Result<()> {
+        for path in &self.paths {
+            path.write_svg_with_markers(w, false, false, compact, dashed_lines)?;
+        }
+        Ok(())
+    }
+
+    /// Write SVG for all paths directly to `w`, attaching markers as in
+    /// [`PathSet::to_svg_with_markers`]; `compact` minifies each path's `d` attribute per
+    /// [`RenderOptions::with_compact_paths`](crate::RenderOptions::with_compact_paths), and
+    /// `dashed_lines` controls squiggle-path dasharrays per
+    /// [`RenderOptions::with_dashed_lines`](crate::RenderOptions::with_dashed_lines)
+    pub fn write_svg_with_markers(
+        &self,
+        w: &mut impl io::Write,
+        ends: &[(bool, bool)],
+        compact: bool,
+        dashed_lines: bool,
+    ) -> io::Result<()> {
+        for (path, &(marker_start, marker_end)) in self.paths.iter().zip(ends) {
+            path.write_svg_with_markers(w, marker_start, marker_end, compact, dashed_lines)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vec2_from_grid() {
+        let a = Vec2::from_grid(0, 0);
+        let b = Vec2::from_grid(1, 0);
+        assert_eq!(b.x - a.x, SCALE * 2.0);
+    }
+
+    #[test]
+    fn test_vec2_from_block_matches_named_anchor_offsets() {
+        let center = Vec2::from_grid(0, 0);
+        assert_eq!(Vec2::from_block(0, 0, Block::Center), center);
+        assert_eq!(Vec2::from_block(0, 0, Block::Top), center.offset(0.0, -0.5));
+        assert_eq!(Vec2::from_block(0, 0, Block::RightQuarterBottom), center.offset(0.5, 0.25));
+    }
+
+    #[test]
+    fn test_line_to_svg() {
+        let path = Path::line_from_grid(0, 0, 2, 0);
+        let svg = path.to_svg();
+        assert!(svg.contains("<path"));
+        assert!(svg.contains("L "));
+    }
+
+    #[test]
+    fn test_squiggle_to_svg() {
+        let path = Path::line_from_grid(0, 0, 2, 0).with_squiggle(true);
+        let svg = path.to_svg();
+        assert!(svg.contains(" Q "));
+        assert!(svg.contains("stroke-dasharray"));
+    }
+
+    #[test]
+    fn test_squiggle_dasharray_can_be_disabled() {
+        let path = Path::line_from_grid(0, 0, 2, 0).with_squiggle(true);
+        let mut buf = Vec::new();
+        path.write_svg_with_markers(&mut buf, false, false, false, false).unwrap();
+        assert!(!String::from_utf8(buf).unwrap().contains("stroke-dasharray"));
+    }
+
+    #[test]
+    fn test_double_line_has_no_dasharray() {
+        let path = Path::line_from_grid(0, 0, 2, 0).with_double(true);
+        let svg = path.to_svg();
+        assert!(!svg.contains("stroke-dasharray"));
+        assert!(svg.contains("stroke-width=\"3\""));
+    }
+
+    #[test]
+    fn test_path_set_queries() {
+        let mut paths = PathSet::new();
+        paths.insert(Path::line_from_grid(0, 0, 2, 0));
+        assert!(paths.left_ends_at(3, 0));
+        assert!(paths.horizontal_passes_through(1, 0));
+        assert!(!paths.vertical_passes_through(1, 0));
+    }
+
+    #[test]
+    fn test_compact_number_strips_leading_and_trailing_zeros() {
+        assert_eq!(compact_number("0.5"), ".5");
+        assert_eq!(compact_number("-0.1"), "-.1");
+        assert_eq!(compact_number("10.50"), "10.5");
+        assert_eq!(compact_number("10.0"), "10");
+        assert_eq!(compact_number("100.25"), "100.25");
+        assert_eq!(compact_number("10"), "10");
+    }
+
+    #[test]
+    fn test_compact_path_data_drops_unambiguous_separators() {
+        assert_eq!(compact_path_data("M 10,-20"), "M10-20");
+        assert_eq!(compact_path_data("M 0.5,0.3"), "M.5.3");
+        assert_eq!(compact_path_data("L 5.5,0.3"), "L5.5.3");
+    }
+
+    #[test]
+    fn test_compact_path_data_keeps_separator_between_two_plain_integers() {
+        // "20" immediately followed by "30" would fuse into "2030" without a separator
+        assert_eq!(compact_path_data("M 10,20 L 20,30"), "M10,20L20,30");
+    }
+
+    #[test]
+    fn test_compact_path_data_round_trips_through_tokenize_d() {
+        let d = "M 10.50,0.5 L -0.1,20.0";
+        let compact = compact_path_data(d);
+        let original: Vec<f64> = tokenize_d(d)
+            .into_iter()
+            .filter(|(kind, _)| *kind == DToken::Number)
+            .map(|(_, t)| t.parse().unwrap())
+            .collect();
+        let compacted: Vec<f64> = tokenize_d(&compact)
+            .into_iter()
+            .filter(|(kind, _)| *kind == DToken::Number)
+            .map(|(_, t)| t.parse().unwrap())
+            .collect();
+        assert_eq!(original, compacted);
+    }
+
+    #[test]
+    fn test_line_to_svg_compact() {
+        let path = Path::line_from_grid(0, 0, 2, 0);
+        let mut buf = Vec::new();
+        path.write_svg(&mut buf, true).unwrap();
+        let svg = String::from_utf8(buf).unwrap();
+        assert!(!svg.contains("M 8,"));
+        assert!(svg.contains("M8,"));
+    }
+}
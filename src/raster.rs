@@ -0,0 +1,99 @@
+//! Headless-friendly color resolution for rasterizing a rendered diagram.
+//!
+//! The SVG [`crate::render`] produces relies on `prefers-color-scheme` and `var(--aasvg-*)` CSS
+//! custom properties so it adapts to the viewer's color scheme in a browser. A rasterizer running
+//! outside a browser (e.g. to produce a PNG for a docs pipeline) can't evaluate either of those,
+//! so [`resolve_theme`] bakes one explicit [`Theme`] into concrete colors first.
+
+/// Which color scheme to bake into a diagram before rasterizing it, since a headless rasterizer
+/// can't evaluate `prefers-color-scheme` itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    /// `--aasvg-stroke/fill/text: #222`, `--aasvg-bg: #fff` (the light `:root` defaults of the stylesheet [`crate::svg`] builds)
+    Light,
+    /// `--aasvg-stroke/fill/text: #eee`, `--aasvg-bg: #1e1e1e` (the `prefers-color-scheme: dark` block)
+    Dark,
+}
+
+impl Theme {
+    /// The concrete `(stroke/fill, text, bg)` hex colors this theme resolves to by default (a
+    /// [`crate::Palette`] passed to [`crate::RenderOptions::with_palette`] overrides these)
+    pub(crate) fn colors(self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            Theme::Light => ("#222", "#222", "#fff"),
+            Theme::Dark => ("#eee", "#eee", "#1e1e1e"),
+        }
+    }
+}
+
+/// Resolve every `var(--aasvg-stroke|fill|text|bg)` reference in `svg` to a concrete color for
+/// `theme`, and drop the now-unnecessary `<style>` block entirely, producing a self-contained SVG
+/// that doesn't depend on any CSS evaluation (custom properties or media queries) from whatever
+/// consumes it next.
+pub(crate) fn resolve_theme(svg: &str, theme: Theme) -> String {
+    let (stroke_and_fill, text, bg) = theme.colors();
+
+    let without_style = match (svg.find("<style>"), svg.find("</style>")) {
+        (Some(start), Some(end)) => {
+            let after = end + "</style>".len();
+            format!("{}{}", &svg[..start], &svg[after..])
+        }
+        _ => svg.to_string(),
+    };
+
+    without_style
+        .replace("var(--aasvg-stroke)", stroke_and_fill)
+        .replace("var(--aasvg-fill)", stroke_and_fill)
+        .replace("var(--aasvg-text)", text)
+        .replace("var(--aasvg-bg)", bg)
+}
+
+/// Why [`crate::render_to_png`] couldn't produce pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RasterError {
+    /// This build has no embeddable SVG rasterizer (e.g. `tiny-skia`/`resvg`) compiled in.
+    ///
+    /// The theme-resolution half of rasterization (baking `prefers-color-scheme` and
+    /// `var(--aasvg-*)` down to concrete colors, see [`resolve_theme`]) needs no new dependency
+    /// and always runs; turning the resulting self-contained SVG into pixels does need one, and
+    /// this source tree has no `Cargo.toml` to declare a `raster` feature or pull in a
+    /// rasterizer crate behind it, so that step can't be implemented here.
+    Unavailable,
+}
+
+impl std::fmt::Display for RasterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RasterError::Unavailable => {
+                write!(f, "no SVG rasterizer is compiled in (the `raster` feature isn't available)")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RasterError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_theme_replaces_css_vars_with_concrete_colors() {
+        let svg = "<svg><style>:root{--aasvg-stroke:#222}</style><rect fill=\"var(--aasvg-bg)\"/><text fill=\"var(--aasvg-text)\"/></svg>";
+        let light = resolve_theme(svg, Theme::Light);
+        assert!(!light.contains("var("));
+        assert!(!light.contains("<style>"));
+        assert!(light.contains("fill=\"#fff\""));
+        assert!(light.contains("fill=\"#222\""));
+
+        let dark = resolve_theme(svg, Theme::Dark);
+        assert!(dark.contains("fill=\"#1e1e1e\""));
+        assert!(dark.contains("fill=\"#eee\""));
+    }
+
+    #[test]
+    fn test_resolve_theme_is_a_no_op_without_a_style_block() {
+        let svg = "<svg><rect fill=\"red\"/></svg>";
+        assert_eq!(resolve_theme(svg, Theme::Light), svg);
+    }
+}
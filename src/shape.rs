@@ -0,0 +1,294 @@
+//! Shape endorsement.
+//!
+//! Individual line `Path`s found by the finder don't know they form a closed
+//! region, so a box can't be filled, stroked as a unit, or given rounded
+//! corners. This pass looks for closed loops in a [`PathSet`] and "endorses"
+//! them into a [`Shape`]: an axis-aligned four-corner loop becomes a `Rect`,
+//! anything else (a loop that includes a diagonal edge, or an axis-aligned
+//! loop with more than four corners) becomes a `Polygon`.
+
+// Many methods are provided for library consumers but not used internally
+#![allow(dead_code)]
+
+use std::io;
+
+use crate::path::{Path, PathKind, PathSet, Vec2, ASPECT, SCALE};
+
+/// The geometric kind of an endorsed [`Shape`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShapeKind {
+    /// A closed four-corner loop made entirely of horizontal/vertical edges
+    Rect,
+    /// Any other closed loop: one that includes at least one diagonal edge, or an axis-aligned
+    /// one with more than four corners
+    Polygon,
+}
+
+/// A closed region endorsed from a boundary of already-found paths
+#[derive(Debug, Clone)]
+pub struct Shape {
+    pub kind: ShapeKind,
+    /// Boundary corners, in order
+    pub points: Vec<Vec2>,
+    /// Fill color (CSS color string), if the region should be painted
+    pub fill: Option<String>,
+    /// Corner radius in SVG user units, only meaningful for `ShapeKind::Rect`
+    pub corner_radius: f64,
+    /// Stacking order relative to the lines/decorations it was endorsed from
+    pub z_order: i32,
+}
+
+impl Shape {
+    /// Endorse a closed axis-aligned loop into a rectangle
+    pub fn rect(points: Vec<Vec2>) -> Self {
+        Self {
+            kind: ShapeKind::Rect,
+            points,
+            fill: None,
+            corner_radius: 0.0,
+            z_order: 0,
+        }
+    }
+
+    /// Endorse a closed loop containing a diagonal edge into a polygon
+    pub fn polygon(points: Vec<Vec2>) -> Self {
+        Self {
+            kind: ShapeKind::Polygon,
+            points,
+            fill: None,
+            corner_radius: 0.0,
+            z_order: 0,
+        }
+    }
+
+    pub fn with_fill(mut self, fill: impl Into<String>) -> Self {
+        self.fill = Some(fill.into());
+        self
+    }
+
+    pub fn with_corner_radius(mut self, radius: f64) -> Self {
+        self.corner_radius = radius;
+        self
+    }
+
+    pub fn with_z_order(mut self, z_order: i32) -> Self {
+        self.z_order = z_order;
+        self
+    }
+
+    /// Generate the SVG element for this shape
+    pub fn to_svg(&self) -> String {
+        let mut buf = Vec::new();
+        self.write_svg(&mut buf)
+            .expect("writing SVG to a Vec<u8> is infallible");
+        String::from_utf8(buf).expect("SVG output is always valid UTF-8")
+    }
+
+    /// Write the SVG element for this shape directly to `w` instead of building an intermediate
+    /// `String` first; [`Shape::to_svg`] is a thin wrapper around this
+    pub fn write_svg(&self, w: &mut impl io::Write) -> io::Result<()> {
+        let fill = self.fill.as_deref().unwrap_or("none");
+
+        match self.kind {
+            ShapeKind::Rect if self.points.len() == 4 => {
+                let xs: Vec<f64> = self.points.iter().map(|p| p.x).collect();
+                let ys: Vec<f64> = self.points.iter().map(|p| p.y).collect();
+                let x = xs.iter().cloned().fold(f64::INFINITY, f64::min);
+                let y = ys.iter().cloned().fold(f64::INFINITY, f64::min);
+                let w_ = xs.iter().cloned().fold(f64::NEG_INFINITY, f64::max) - x;
+                let h = ys.iter().cloned().fold(f64::NEG_INFINITY, f64::max) - y;
+                writeln!(
+                    w,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\" fill=\"{}\" stroke=\"var(--aasvg-stroke)\"/>",
+                    x, y, w_, h, self.corner_radius, fill
+                )
+            }
+            _ => {
+                let pts = self
+                    .points
+                    .iter()
+                    .map(|p| format!("{},{}", p.x, p.y))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                writeln!(
+                    w,
+                    "<polygon points=\"{}\" fill=\"{}\" stroke=\"var(--aasvg-stroke)\"/>",
+                    pts, fill
+                )
+            }
+        }
+    }
+}
+
+/// Collection of endorsed shapes
+#[derive(Debug, Default)]
+pub struct ShapeSet {
+    shapes: Vec<Shape>,
+}
+
+impl ShapeSet {
+    pub fn new() -> Self {
+        Self { shapes: Vec::new() }
+    }
+
+    pub fn insert(&mut self, shape: Shape) {
+        self.shapes.push(shape);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Shape> {
+        self.shapes.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    /// Generate SVG for all endorsed shapes
+    pub fn to_svg(&self) -> String {
+        let mut buf = Vec::new();
+        self.write_svg(&mut buf)
+            .expect("writing SVG to a Vec<u8> is infallible");
+        String::from_utf8(buf).expect("SVG output is always valid UTF-8")
+    }
+
+    /// Write SVG for all endorsed shapes directly to `w`; [`ShapeSet::to_svg`] is a thin wrapper
+    /// around this
+    pub fn write_svg(&self, w: &mut impl io::Write) -> io::Result<()> {
+        for shape in &self.shapes {
+            shape.write_svg(w)?;
+        }
+        Ok(())
+    }
+}
+
+/// If `path` is a closed polyline (its start and end coincide), return its distinct corner
+/// points in order; otherwise `None`. Expects to run after `PathSet::merge_contacts`, which is
+/// what turns a box's four separate sides into one polyline that loops back on itself.
+///
+/// The last contact in a loop never goes through `merge_contacts`'s own join search (there's only
+/// one path left by then for it to "meet"), so it closes this same half-cell gap as a straight
+/// line's center-anchored endpoint meeting a diagonal's corner-anchored one (see
+/// `PathSet::merge_contacts`'s doc comment).
+fn closed_loop_points(path: &Path) -> Option<Vec<Vec2>> {
+    if path.kind != PathKind::Polyline || path.segments.len() < 3 {
+        return None;
+    }
+    let start = path.start;
+    let end = path.end();
+    if (start.x - end.x).abs() > SCALE + 0.01 || (start.y - end.y).abs() > SCALE * ASPECT + 0.01 {
+        return None;
+    }
+
+    let mut points = vec![start];
+    points.extend(path.segments.iter().map(|s| s.to));
+    points.pop(); // the last point just repeats `start`, closing the loop
+    Some(points)
+}
+
+/// Whether a closed loop's corners alternate strictly horizontal/vertical edges (no diagonals)
+fn is_axis_aligned(points: &[Vec2]) -> bool {
+    (0..points.len()).all(|i| {
+        let a = points[i];
+        let b = points[(i + 1) % points.len()];
+        (a.x - b.x).abs() < 0.01 || (a.y - b.y).abs() < 0.01
+    })
+}
+
+/// Endorse closed loops in `paths` into `shapes`, removing the paths that made them up so the
+/// renderer doesn't also draw them as separate lines. A four-corner loop whose edges are all
+/// horizontal/vertical becomes a `Rect`; any other loop (a diagonal edge, or more than four
+/// corners) becomes a `Polygon`.
+pub fn endorse(paths: &mut PathSet, shapes: &mut ShapeSet) {
+    let mut remove = Vec::new();
+    let mut new_shapes = Vec::new();
+
+    for (index, path) in paths.iter().enumerate() {
+        let Some(points) = closed_loop_points(path) else {
+            continue;
+        };
+
+        let shape = if points.len() == 4 && is_axis_aligned(&points) {
+            Shape::rect(points)
+        } else {
+            Shape::polygon(points)
+        };
+        new_shapes.push(shape);
+        remove.push(index);
+    }
+
+    if remove.is_empty() {
+        return;
+    }
+    paths.retain_indices(&remove);
+    for shape in new_shapes {
+        shapes.insert(shape);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    #[test]
+    fn test_endorse_simple_box() {
+        let mut paths = PathSet::new();
+        paths.insert(Path::line_from_grid(0, 0, 4, 0));
+        paths.insert(Path::line_from_grid(0, 2, 4, 2));
+        paths.insert(Path::line_from_grid(0, 0, 0, 2));
+        paths.insert(Path::line_from_grid(4, 0, 4, 2));
+        paths.merge_contacts();
+
+        let mut shapes = ShapeSet::new();
+        endorse(&mut paths, &mut shapes);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(paths.len(), 0);
+    }
+
+    #[test]
+    fn test_no_endorse_for_open_lines() {
+        let mut paths = PathSet::new();
+        paths.insert(Path::line_from_grid(0, 0, 4, 0));
+        paths.insert(Path::line_from_grid(0, 2, 4, 2));
+        paths.merge_contacts();
+
+        let mut shapes = ShapeSet::new();
+        endorse(&mut paths, &mut shapes);
+
+        assert!(shapes.is_empty());
+        assert_eq!(paths.len(), 2);
+    }
+
+    #[test]
+    fn test_endorse_diamond_made_entirely_of_diagonals() {
+        let mut grid = Grid::new(" /\\\n/  \\\n\\  /\n \\/");
+        let mut paths = PathSet::new();
+        crate::finder::find_paths(&mut grid, &mut paths, 0.0);
+
+        let mut shapes = ShapeSet::new();
+        endorse(&mut paths, &mut shapes);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(paths.len(), 0);
+        assert_eq!(shapes.iter().next().unwrap().kind, ShapeKind::Polygon);
+    }
+
+    #[test]
+    fn test_endorse_hexagon_mixing_straight_and_diagonal_edges() {
+        let mut grid = Grid::new(" .--.\n/    \\\n\\    /\n `--'");
+        let mut paths = PathSet::new();
+        crate::finder::find_paths(&mut grid, &mut paths, 0.0);
+
+        let mut shapes = ShapeSet::new();
+        endorse(&mut paths, &mut shapes);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(paths.len(), 0);
+        assert_eq!(shapes.iter().next().unwrap().kind, ShapeKind::Polygon);
+    }
+}
@@ -0,0 +1,489 @@
+//! SVG document generation.
+//!
+//! Assembles the paths, decorations and text extracted from the grid into a
+//! single self-contained SVG document with light/dark mode CSS variables.
+
+use crate::chars::CharSet;
+use crate::decoration::{ArrowStyle, DecorationSet, DecorationType, ARROW_MARKER_DEFS, CSS_CLASSES_STYLESHEET};
+use crate::fill::FillRule;
+use crate::grid::Grid;
+use crate::path::{PathSet, Vec2, ASPECT, SCALE};
+use crate::raster::Theme;
+use crate::shape::ShapeSet;
+
+/// An explicit set of CSS colors for the diagram's stroke, fill, background, and text, overriding
+/// the built-in light/dark defaults (see [`RenderOptions::with_palette`]). Each field takes any
+/// valid CSS color string (`"#222"`, `"rebeccapurple"`, `"var(--site-accent)"`, etc.), so a
+/// consumer can brand a diagram or match a site's existing design tokens.
+#[derive(Debug, Clone)]
+pub struct Palette {
+    pub stroke: String,
+    pub fill: String,
+    pub bg: String,
+    pub text: String,
+}
+
+impl Palette {
+    /// Build a palette from explicit CSS color strings
+    pub fn new(stroke: impl Into<String>, fill: impl Into<String>, bg: impl Into<String>, text: impl Into<String>) -> Self {
+        Self { stroke: stroke.into(), fill: fill.into(), bg: bg.into(), text: text.into() }
+    }
+
+    /// The built-in palette for a given [`Theme`]
+    fn from_theme(theme: Theme) -> Self {
+        let (stroke_and_fill, text, bg) = theme.colors();
+        Self::new(stroke_and_fill, stroke_and_fill, bg, text)
+    }
+}
+
+/// Options controlling how a diagram is rendered to SVG
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+    /// Number of consecutive spaces that terminate a text run
+    spaces: u32,
+    /// Draw a background rect behind the diagram
+    backdrop: bool,
+    /// Skip extracting and rendering text runs entirely
+    disable_text: bool,
+    /// Stretch the viewBox to fill the available space instead of sizing to content
+    stretch: bool,
+    /// How enclosed regions are told apart from unenclosed ones during region fill
+    fill_rule: FillRule,
+    /// Radius, in grid-cell units (`0.0..=0.5`), used to round both explicit `.`/`'` vertices and
+    /// the `+`/L-shaped joints of open merged polylines. A fully closed box, endorsed into a
+    /// rectangle shape, keeps square corners whatever the radius. `0.0` keeps hard miter corners.
+    corner_radius: f64,
+    /// Whether arrowheads are drawn as inline polygons or shared `<marker>` references
+    arrow_style: ArrowStyle,
+    /// Whether decorations carry semantic `aasvg-*` classes instead of inline presentation
+    /// attributes, for restyling from external CSS
+    css_classes: bool,
+    /// Character classification table used to parse the diagram
+    charset: CharSet,
+    /// If set, emit a single explicit-theme stylesheet (a plain `:root` block) instead of a
+    /// `prefers-color-scheme` media query that switches between light and dark
+    theme: Option<Theme>,
+    /// Custom colors overriding the built-in light/dark defaults
+    palette: Option<Palette>,
+    /// Minify every path's `d` attribute (see [`Self::with_compact_paths`])
+    compact_paths: bool,
+    /// Give `~` squiggle lines a `stroke-dasharray` in addition to their wavy geometry (see
+    /// [`Self::with_dashed_lines`])
+    dashed_lines: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            spaces: 1,
+            backdrop: false,
+            disable_text: false,
+            stretch: false,
+            fill_rule: FillRule::default(),
+            corner_radius: 0.0,
+            arrow_style: ArrowStyle::default(),
+            css_classes: false,
+            charset: CharSet::default(),
+            theme: None,
+            palette: None,
+            compact_paths: false,
+            dashed_lines: true,
+        }
+    }
+}
+
+impl RenderOptions {
+    /// Create a new set of options with the default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how many consecutive spaces terminate a text run (default: 1)
+    pub fn with_spaces(mut self, spaces: u32) -> Self {
+        self.spaces = spaces;
+        self
+    }
+
+    /// Draw a background rect behind the diagram (default: false)
+    pub fn with_backdrop(mut self, backdrop: bool) -> Self {
+        self.backdrop = backdrop;
+        self
+    }
+
+    /// Skip extracting and rendering text runs entirely (default: false)
+    pub fn with_disable_text(mut self, disable_text: bool) -> Self {
+        self.disable_text = disable_text;
+        self
+    }
+
+    /// Stretch the viewBox to fill the available space (default: false)
+    pub fn with_stretch(mut self, stretch: bool) -> Self {
+        self.stretch = stretch;
+        self
+    }
+
+    /// Set the winding rule used to decide whether a blank region is enclosed and should be
+    /// filled (default: [`FillRule::NonZero`])
+    pub fn with_fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.fill_rule = fill_rule;
+        self
+    }
+
+    pub(crate) fn fill_rule(&self) -> FillRule {
+        self.fill_rule
+    }
+
+    /// Set the corner-rounding radius, in grid-cell units, clamped to `0.0..=0.5` (default: `0.0`,
+    /// hard miter corners)
+    pub fn with_corner_radius(mut self, corner_radius: f64) -> Self {
+        self.corner_radius = corner_radius.clamp(0.0, 0.5);
+        self
+    }
+
+    pub(crate) fn corner_radius(&self) -> f64 {
+        self.corner_radius
+    }
+
+    /// Choose how arrowheads are drawn (default: [`ArrowStyle::Polygon`])
+    pub fn with_arrow_style(mut self, arrow_style: ArrowStyle) -> Self {
+        self.arrow_style = arrow_style;
+        self
+    }
+
+    pub(crate) fn arrow_style(&self) -> ArrowStyle {
+        self.arrow_style
+    }
+
+    /// Emit decorations with semantic `aasvg-*` classes instead of inline `fill`/`stroke`
+    /// attributes, so they can be restyled from external CSS without regenerating the diagram
+    /// (default: `false`, matching the original inline-attribute output)
+    pub fn with_css_classes(mut self, css_classes: bool) -> Self {
+        self.css_classes = css_classes;
+        self
+    }
+
+    pub(crate) fn css_classes(&self) -> bool {
+        self.css_classes
+    }
+
+    /// Use a custom character classification table to parse the diagram, instead of
+    /// [`CharSet::default`]'s built-in ASCII/box-drawing vocabulary. Lets a consumer add new
+    /// point glyphs, register custom gray-fill shading levels, or disable a line style like
+    /// squiggles without forking the crate.
+    pub fn with_charset(mut self, charset: CharSet) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    pub(crate) fn charset(&self) -> &CharSet {
+        &self.charset
+    }
+
+    /// Emit a single explicit-theme stylesheet (default: `None`, a `prefers-color-scheme` media
+    /// query that switches between the light and dark palettes). Useful for environments that
+    /// don't evaluate that media query, e.g. a headless rasterizer (see [`crate::render_to_png`]).
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    pub(crate) fn theme(&self) -> Option<Theme> {
+        self.theme
+    }
+
+    /// Override the built-in light/dark colors with an explicit [`Palette`] (default: `None`, the
+    /// built-in `#222`-on-`#fff`/`#eee`-on-`#1e1e1e` palettes). Combine with [`Self::with_theme`]
+    /// to brand a single-theme diagram; without it, the palette replaces both the light and dark
+    /// `:root` blocks, so it still renders identically either way.
+    pub fn with_palette(mut self, palette: Palette) -> Self {
+        self.palette = Some(palette);
+        self
+    }
+
+    pub(crate) fn palette(&self) -> Option<&Palette> {
+        self.palette.as_ref()
+    }
+
+    /// Minify every path's `d` attribute the way svgtypes' `WriteOptions` does: drop leading zeros
+    /// in fractional numbers, strip trailing fractional zeros, and omit separators between adjacent
+    /// numbers wherever a sign or decimal point already delimits them unambiguously (default:
+    /// `false`, the original space/comma-separated output). Shrinks output size for large diagrams
+    /// at no cost to the rendered geometry.
+    pub fn with_compact_paths(mut self, compact_paths: bool) -> Self {
+        self.compact_paths = compact_paths;
+        self
+    }
+
+    pub(crate) fn compact_paths(&self) -> bool {
+        self.compact_paths
+    }
+
+    /// Give `~` squiggle lines a `stroke-dasharray` in addition to their existing wavy geometry, so
+    /// they also read as visually distinct from a plain `-` stroke at a glance (default: `true`).
+    /// `=` double lines and `-`/`|` solid lines are unaffected either way: `=` already renders with
+    /// a thicker stroke-width, which this crate treats as its own distinct style rather than
+    /// stacking a dasharray on top of it. Set to `false` to render `~` as a plain solid wavy stroke,
+    /// matching this crate's output before this option existed.
+    pub fn with_dashed_lines(mut self, dashed_lines: bool) -> Self {
+        self.dashed_lines = dashed_lines;
+        self
+    }
+
+    pub(crate) fn dashed_lines(&self) -> bool {
+        self.dashed_lines
+    }
+}
+
+/// Escape characters that are special in SVG/XML text content
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Build the `<style>` block that declares the `--aasvg-*` CSS custom properties paths/decorations
+/// reference via `var(...)`. With `options.theme()` unset this is a `prefers-color-scheme` media
+/// query switching between a light and dark palette (each defaulting to the built-in colors,
+/// overridable via `options.palette()`); with it set, a single explicit-theme `:root` block using
+/// just that theme's (or the override palette's) colors.
+fn build_stylesheet(options: &RenderOptions) -> String {
+    match options.theme() {
+        Some(theme) => {
+            let palette = options.palette().cloned().unwrap_or_else(|| Palette::from_theme(theme));
+            format!(
+                "<style>\n:root {{\n  --aasvg-stroke: {};\n  --aasvg-fill: {};\n  --aasvg-bg: {};\n  --aasvg-text: {};\n}}\n</style>\n",
+                palette.stroke, palette.fill, palette.bg, palette.text
+            )
+        }
+        None => {
+            let light = options.palette().cloned().unwrap_or_else(|| Palette::from_theme(Theme::Light));
+            let dark = options.palette().cloned().unwrap_or_else(|| Palette::from_theme(Theme::Dark));
+            format!(
+                "<style>\n:root {{\n  --aasvg-stroke: {};\n  --aasvg-fill: {};\n  --aasvg-bg: {};\n  --aasvg-text: {};\n}}\n@media (prefers-color-scheme: dark) {{\n  :root {{\n    --aasvg-stroke: {};\n    --aasvg-fill: {};\n    --aasvg-bg: {};\n    --aasvg-text: {};\n  }}\n}}\n</style>\n",
+                light.stroke, light.fill, light.bg, light.text, dark.stroke, dark.fill, dark.bg, dark.text,
+            )
+        }
+    }
+}
+
+/// Extract and render any remaining (unused) cells as `<text>` elements
+fn render_text(grid: &mut Grid, options: &RenderOptions) -> String {
+    let mut result = String::new();
+    if options.disable_text {
+        return result;
+    }
+
+    for y in 0..grid.height as i32 {
+        let mut x = 0;
+        while x < grid.width as i32 {
+            if let Some(start_x) = grid.text_start(x, y, options.spaces) {
+                let style = grid.style_at(start_x, y).map(str::to_string);
+                let text = grid.extract_text(start_x, y, options.spaces);
+                let len = text.chars().count() as i32;
+                x = start_x + len;
+                if text.trim().is_empty() {
+                    continue;
+                }
+                // A single-character run that carries a legend key (see `Grid::styles`) gets its
+                // declared CSS properties tacked on as an inline `style` attribute; a
+                // multi-character run never matches since legend keys are single marker characters.
+                let style_attr = if len == 1 {
+                    style.map(|css| format!(" style=\"{}\"", escape_xml(&css))).unwrap_or_default()
+                } else {
+                    String::new()
+                };
+                let pos = Vec2::from_grid(start_x, y).offset(-0.5, 0.0);
+                result.push_str(&format!(
+                    "<text x=\"{}\" y=\"{}\" fill=\"var(--aasvg-text)\" font-family=\"monospace\"{}>{}</text>\n",
+                    pos.x,
+                    pos.y + SCALE * ASPECT * 0.35,
+                    style_attr,
+                    escape_xml(&text)
+                ));
+            } else {
+                x += 1;
+            }
+        }
+    }
+
+    result
+}
+
+/// Render each quoted literal text span (see `Grid::literals`) as its own `<text>` element,
+/// bypassing the normal run-based extraction entirely since its cells were already blanked out
+/// during grid construction
+fn render_literals(grid: &Grid, options: &RenderOptions) -> String {
+    let mut result = String::new();
+    if options.disable_text {
+        return result;
+    }
+
+    for (x, y, text) in grid.literals() {
+        let pos = Vec2::from_grid(x, y).offset(-0.5, 0.0);
+        result.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" fill=\"var(--aasvg-text)\" font-family=\"monospace\">{}</text>\n",
+            pos.x,
+            pos.y + SCALE * ASPECT * 0.35,
+            escape_xml(&text)
+        ));
+    }
+
+    result
+}
+
+/// Match each `Arrow` decoration to the path endpoint it sits on. An arrowhead character sits one
+/// whole grid cell past where `find_arrow_heads` leaves the line it belongs to (the line finders
+/// never consume the arrowhead's own cell), so the tolerance here spans a bit past a full cell —
+/// wider than `PathSet::merge_contacts`'s half-cell touching tolerance, which instead looks for
+/// two path ends landing in the very same spot. Returns, in [`PathSet::iter`] order, which end of
+/// each path should carry a marker reference, and in [`DecorationSet::iter`] order, which arrows
+/// were matched (and so should be skipped when the decorations themselves are drawn). An arrow
+/// that doesn't land near any path endpoint (e.g. a standalone one) is left unmatched, so the
+/// caller can still fall back to drawing it as its own polygon.
+fn match_arrow_markers(paths: &PathSet, decorations: &DecorationSet) -> (Vec<(bool, bool)>, Vec<bool>) {
+    let thresh_x = SCALE * 2.0 * 1.25;
+    let thresh_y = SCALE * ASPECT * 2.0 * 1.25;
+    let near = |a: Vec2, b: Vec2| (a.x - b.x).abs() <= thresh_x && (a.y - b.y).abs() <= thresh_y;
+
+    let mut ends = vec![(false, false); paths.len()];
+    let mut consumed = vec![false; decorations.len()];
+
+    for (di, decoration) in decorations.iter().enumerate() {
+        if decoration.kind != DecorationType::Arrow {
+            continue;
+        }
+        for (pi, path) in paths.iter().enumerate() {
+            if near(decoration.pos, path.start) {
+                ends[pi].0 = true;
+                consumed[di] = true;
+                break;
+            }
+            if near(decoration.pos, path.end()) {
+                ends[pi].1 = true;
+                consumed[di] = true;
+                break;
+            }
+        }
+    }
+
+    (ends, consumed)
+}
+
+/// Write a diagram's grid, paths, shapes and decorations as a complete SVG document directly to
+/// `w`, instead of assembling the whole thing as one large intermediate `String` first; useful
+/// for big diagrams streamed straight to a file or socket via [`crate::render_to_writer`].
+/// [`crate::render_with_options`] is a thin wrapper around this that collects the output into a
+/// `String` via a `Vec<u8>` buffer.
+pub fn write_svg(
+    w: &mut impl std::io::Write,
+    grid: &mut Grid,
+    paths: &PathSet,
+    shapes: &ShapeSet,
+    decorations: &DecorationSet,
+    options: &RenderOptions,
+) -> std::io::Result<()> {
+    let cell_w = SCALE * 2.0;
+    let cell_h = SCALE * ASPECT * 2.0;
+    let width = grid.width as f64 * cell_w;
+    let height = grid.height as f64 * cell_h;
+
+    writeln!(
+        w,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\"{}>",
+        width,
+        height,
+        if options.stretch {
+            " width=\"100%\" height=\"100%\" preserveAspectRatio=\"none\""
+        } else {
+            ""
+        }
+    )?;
+    w.write_all(build_stylesheet(options).as_bytes())?;
+    if options.css_classes {
+        w.write_all(CSS_CLASSES_STYLESHEET.as_bytes())?;
+    }
+
+    if options.backdrop {
+        writeln!(
+            w,
+            "<rect x=\"0\" y=\"0\" width=\"{}\" height=\"{}\" fill=\"var(--aasvg-bg)\"/>",
+            width, height
+        )?;
+    }
+
+    shapes.write_svg(w)?;
+    match options.arrow_style() {
+        ArrowStyle::Polygon => {
+            paths.write_svg(w, options.compact_paths(), options.dashed_lines())?;
+            decorations.write_svg_styled(w, options.css_classes())?;
+        }
+        ArrowStyle::Marker => {
+            let (ends, consumed) = match_arrow_markers(paths, decorations);
+            w.write_all(ARROW_MARKER_DEFS.as_bytes())?;
+            paths.write_svg_with_markers(w, &ends, options.compact_paths(), options.dashed_lines())?;
+            decorations.write_svg_skipping_styled(&consumed, w, options.css_classes())?;
+        }
+    }
+    w.write_all(render_text(grid, options).as_bytes())?;
+    w.write_all(render_literals(grid, options).as_bytes())?;
+
+    w.write_all(b"</svg>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoration::DecorationSet;
+    use crate::finder::{find_decorations, find_paths};
+    use crate::shape;
+
+    #[test]
+    fn test_generate_svg_basic() {
+        let mut grid = Grid::new("+--+\n|  |\n+--+");
+        let mut paths = PathSet::new();
+        let mut shapes = ShapeSet::new();
+        let mut decorations = DecorationSet::new();
+        find_paths(&mut grid, &mut paths, 0.0);
+        shape::endorse(&mut paths, &mut shapes);
+        find_decorations(&mut grid, &paths, &mut decorations);
+
+        let mut buf = Vec::new();
+        write_svg(&mut buf, &mut grid, &paths, &shapes, &decorations, &RenderOptions::default()).unwrap();
+        let svg = String::from_utf8(buf).unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>"));
+        assert!(svg.contains("--aasvg-stroke"));
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(escape_xml("<a>&\""), "&lt;a&gt;&amp;&quot;");
+    }
+
+    #[test]
+    fn test_default_stylesheet_has_prefers_color_scheme_media_query() {
+        let css = build_stylesheet(&RenderOptions::default());
+        assert!(css.contains("@media (prefers-color-scheme: dark)"));
+        assert!(css.contains("--aasvg-stroke: #222"));
+        assert!(css.contains("--aasvg-stroke: #eee"));
+    }
+
+    #[test]
+    fn test_with_theme_drops_the_media_query() {
+        let options = RenderOptions::new().with_theme(Theme::Dark);
+        let css = build_stylesheet(&options);
+        assert!(!css.contains("@media"));
+        assert!(css.contains("--aasvg-bg: #1e1e1e"));
+    }
+
+    #[test]
+    fn test_with_palette_overrides_default_colors() {
+        let palette = Palette::new("red", "red", "white", "black");
+        let options = RenderOptions::new().with_theme(Theme::Light).with_palette(palette);
+        let css = build_stylesheet(&options);
+        assert!(css.contains("--aasvg-stroke: red"));
+        assert!(css.contains("--aasvg-bg: white"));
+        assert!(!css.contains("#222"));
+    }
+}
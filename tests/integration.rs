@@ -22,7 +22,8 @@ fn test_render_simple_box() {
 
     assert!(svg.starts_with("<svg"));
     assert!(svg.ends_with("</svg>"));
-    assert!(svg.contains("<path")); // Lines
+    // A fully closed box is endorsed into a single rect rather than four lines
+    assert!(svg.contains("<rect"));
     assert!(svg.contains("var(--aasvg-stroke)")); // CSS variable usage
 }
 